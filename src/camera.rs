@@ -1,15 +1,554 @@
 //! Camera setup.
 
-use bevy::prelude::*;
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+    render::{
+        camera::{RenderTarget, ScalingMode, Viewport},
+        render_resource::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
+        texture::{BevyDefault, ImageSampler},
+        view::RenderLayers,
+    },
+    window::{PrimaryWindow, WindowResized},
+};
+
+use crate::{game::spawn::player::Player, AppSet};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(Startup, spawn_camera);
+
+    // Zoom the camera with the mouse wheel.
+    app.register_type::<(CameraZoom, CameraPunch)>();
+    app.add_systems(
+        Update,
+        (
+            tick_camera_punch.in_set(AppSet::TickTimers),
+            camera_zoom_system.in_set(AppSet::Update),
+        ),
+    );
+
+    // Follow the player around the playfield while zoomed in.
+    app.register_type::<CameraDeadzone>();
+    app.add_systems(Update, camera_follow_system.in_set(AppSet::Update));
+
+    // Keep the playfield in view regardless of the window shape, rendered at a fraction of
+    // the window's resolution if asked to.
+    // Not in an `AppSet`, so the window is refitted in photo mode too.
+    app.register_type::<(CameraFit, RenderScale, CrispUpscaling, RenderBlit)>();
+    app.init_resource::<CameraFit>();
+    app.init_resource::<RenderScale>();
+    app.init_resource::<CrispUpscaling>();
+    app.add_systems(
+        Update,
+        (apply_camera_fit, apply_render_scale).chain().run_if(
+            resource_changed::<CameraFit>
+                .or_else(resource_changed::<RenderScale>)
+                .or_else(resource_changed::<CrispUpscaling>)
+                .or_else(on_event::<WindowResized>()),
+        ),
+    );
+}
+
+/// Size of the playfield in world units.
+/// The camera is framed around this area (see [`CameraFit`]),
+/// so edge logic should use it instead of the window size.
+pub const BOUNDS: Vec2 = Vec2::new(1200.0, 640.0);
+
+/// How the playfield is mapped onto windows whose aspect ratio doesn't match [`BOUNDS`].
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Resource)]
+pub enum CameraFit {
+    /// Show the full height of the playfield, with bars on the sides of wide windows.
+    #[default]
+    FitHeight,
+    /// Show the full width of the playfield, with bars above and below tall windows.
+    FitWidth,
+    /// Stretch the playfield to fill the window.
+    Stretch,
+}
+
+/// Fraction of the window's resolution the playfield is rendered at, upscaled to fill the
+/// playfield's part of the window. At 1 the playfield is rendered straight to the window.
+/// The UI is always drawn at the window's resolution.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Resource)]
+pub struct RenderScale(pub f32);
+
+impl Default for RenderScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
 }
 
+/// Upscale a scaled-down playfield with nearest-neighbour sampling, keeping pixels sharp,
+/// instead of smoothing them out.
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Resource)]
+pub struct CrispUpscaling(pub bool);
+
+/// The render layer of the scaled-down playfield's image and the camera that draws it.
+const BLIT_LAYER: usize = 1;
+
+/// The sprite that shows the scaled-down playfield in the window.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct RenderBlit;
+
 fn spawn_camera(mut commands: Commands) {
     commands.spawn((
         Name::new("Camera"),
         Camera2dBundle::default(),
+        CameraZoom::default(),
+        CameraPunch::default(),
+        CameraDeadzone::default(),
+    ));
+
+    // draws the UI, and the playfield's image when it is rendered scaled down
+    commands.spawn((
+        Name::new("Blit Camera"),
+        Camera2dBundle {
+            camera: Camera {
+                order: 1,
+                clear_color: ClearColorConfig::None,
+                ..default()
+            },
+            ..default()
+        },
+        RenderLayers::layer(BLIT_LAYER),
         IsDefaultUiCamera,
     ));
+    commands.spawn((
+        Name::new("Render Blit"),
+        SpriteBundle {
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        RenderLayers::layer(BLIT_LAYER),
+        RenderBlit,
+    ));
+}
+
+/// Converts a position in the window, such as the cursor's, to the world through the game
+/// camera, whether the playfield is rendered to the window or to a scaled-down image.
+pub fn window_to_world(
+    position: Vec2,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    blit_camera: &Camera,
+) -> Option<Vec2> {
+    let position = match (&camera.target, blit_camera.logical_viewport_rect()) {
+        (RenderTarget::Image(_), Some(viewport)) => {
+            let image_size = camera.logical_viewport_size()?;
+            (position - viewport.min) / viewport.size() * image_size
+        }
+        _ => position,
+    };
+    camera.viewport_to_world_2d(camera_transform, position)
+}
+
+/// Smallest projection scale, i.e. the most zoomed in the camera can be.
+const MIN_ZOOM: f32 = 0.5;
+/// Largest projection scale, i.e. the most zoomed out the camera can be.
+const MAX_ZOOM: f32 = 2.0;
+/// Change in projection scale per line scrolled.
+const ZOOM_STEP: f32 = 0.1;
+/// How quickly the projection scale catches up with the target, per second.
+const ZOOM_SMOOTHING: f32 = 10.0;
+
+/// The projection scale the camera is easing toward.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct CameraZoom {
+    pub target: f32,
+    /// The eased scale, before any [`CameraPunch`] is applied.
+    current: f32,
+}
+
+impl Default for CameraZoom {
+    fn default() -> Self {
+        Self {
+            target: 1.0,
+            current: 1.0,
+        }
+    }
+}
+
+/// A quick zoom in and back out, layered on top of [`CameraZoom`].
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct CameraPunch {
+    /// Fraction of the projection scale removed at the peak of the punch.
+    pub amount: f32,
+    pub timer: Timer,
+}
+
+impl Default for CameraPunch {
+    fn default() -> Self {
+        let duration = 0.15;
+        let mut timer = Timer::from_seconds(duration, TimerMode::Once);
+        // Start finished so the camera isn't punched when it spawns.
+        timer.set_elapsed(timer.duration());
+        Self {
+            amount: 0.04,
+            timer,
+        }
+    }
+}
+
+impl CameraPunch {
+    /// Restart the punch from the beginning.
+    pub fn start(&mut self) {
+        self.timer.reset();
+    }
+
+    /// Multiplier for the projection scale: dips to `1 - amount` halfway through, then recovers.
+    fn scale_multiplier(&self) -> f32 {
+        if self.timer.finished() {
+            return 1.0;
+        }
+        1.0 - self.amount * (std::f32::consts::PI * self.timer.fraction()).sin()
+    }
+}
+
+fn tick_camera_punch(time: Res<Time>, mut punch_query: Query<&mut CameraPunch>) {
+    for mut punch in &mut punch_query {
+        punch.timer.tick(time.delta());
+    }
+}
+
+fn camera_zoom_system(
+    time: Res<Time>,
+    mut wheel_events: EventReader<MouseWheel>,
+    mut camera_query: Query<(&mut CameraZoom, &CameraPunch, &mut OrthographicProjection)>,
+) {
+    // Scrolling up zooms in, which means a smaller projection scale.
+    let scrolled: f32 = wheel_events
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            // Pixel deltas (e.g. from touchpads) are much larger than line deltas.
+            MouseScrollUnit::Pixel => event.y / 100.0,
+        })
+        .sum();
+
+    for (mut zoom, punch, mut projection) in &mut camera_query {
+        zoom.target = (zoom.target - scrolled * ZOOM_STEP).clamp(MIN_ZOOM, MAX_ZOOM);
+
+        let t = (ZOOM_SMOOTHING * time.delta_seconds()).min(1.0);
+        zoom.current = zoom.current.lerp(zoom.target, t);
+        projection.scale = zoom.current * punch.scale_multiplier();
+    }
+}
+
+/// The camera stays put while the player is within `half_extents` of its centre, in world
+/// units, and catches up once the player leaves that rectangle.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct CameraDeadzone {
+    pub half_extents: Vec2,
+}
+
+impl Default for CameraDeadzone {
+    fn default() -> Self {
+        Self {
+            half_extents: Vec2::new(120.0, 80.0),
+        }
+    }
+}
+
+/// How quickly the camera catches up with the edge of its deadzone, per second.
+const FOLLOW_SMOOTHING: f32 = 5.0;
+
+/// Moves the camera just enough to bring the player back inside its [`CameraDeadzone`],
+/// without ever showing past the edges of the playfield. Zoomed all the way out, the whole
+/// playfield is in view and the camera stays centred.
+fn camera_follow_system(
+    time: Res<Time>,
+    player_query: Query<&Transform, With<Player>>,
+    mut camera_query: Query<(&CameraDeadzone, &CameraZoom, &mut Transform), Without<Player>>,
+) {
+    // no warning here: the camera also runs on screens without a player
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player = player_transform.translation.xy();
+
+    for (deadzone, zoom, mut transform) in &mut camera_query {
+        let camera = transform.translation.xy();
+        let offset = player - camera;
+        let overshoot = offset - offset.clamp(-deadzone.half_extents, deadzone.half_extents);
+
+        // the camera can move until the edge of its view meets the edge of the playfield
+        let max_offset = (BOUNDS / 2.0 * (1.0 - zoom.current)).max(Vec2::ZERO);
+        let target = (camera + overshoot).clamp(-max_offset, max_offset);
+
+        let t = (FOLLOW_SMOOTHING * time.delta_seconds()).min(1.0);
+        transform.translation = camera.lerp(target, t).extend(transform.translation.z);
+    }
+}
+
+/// Frames the playfield in the window. The viewport goes on the blit camera, so the UI always
+/// lines up with the playfield, and [`apply_render_scale`] passes it on to the game camera.
+fn apply_camera_fit(
+    fit: Res<CameraFit>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut projection_query: Query<&mut OrthographicProjection, With<CameraZoom>>,
+    mut blit_camera_query: Query<&mut Camera, With<IsDefaultUiCamera>>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let window_size = UVec2::new(window.physical_width(), window.physical_height());
+    if window_size.x == 0 || window_size.y == 0 {
+        return;
+    }
+
+    let aspect_ratio = BOUNDS.x / BOUNDS.y;
+    let (scaling_mode, viewport_size) = match *fit {
+        CameraFit::FitHeight => (
+            ScalingMode::FixedVertical(BOUNDS.y),
+            UVec2::new(
+                window_size
+                    .x
+                    .min((window_size.y as f32 * aspect_ratio) as u32),
+                window_size.y,
+            ),
+        ),
+        CameraFit::FitWidth => (
+            ScalingMode::FixedHorizontal(BOUNDS.x),
+            UVec2::new(
+                window_size.x,
+                window_size
+                    .y
+                    .min((window_size.x as f32 / aspect_ratio) as u32),
+            ),
+        ),
+        CameraFit::Stretch => (
+            ScalingMode::Fixed {
+                width: BOUNDS.x,
+                height: BOUNDS.y,
+            },
+            window_size,
+        ),
+    };
+
+    for mut projection in &mut projection_query {
+        projection.scaling_mode = scaling_mode;
+    }
+    for mut camera in &mut blit_camera_query {
+        // Center the viewport; the area around it is left as the clear color.
+        camera.viewport = Some(Viewport {
+            physical_position: (window_size - viewport_size) / 2,
+            physical_size: viewport_size,
+            ..default()
+        });
+    }
+}
+
+/// Renders the game camera straight into the playfield's viewport at a [`RenderScale`] of 1.
+/// Below that, it renders to an image that many times the viewport's size, which the blit
+/// camera stretches back over the viewport.
+fn apply_render_scale(
+    render_scale: Res<RenderScale>,
+    crisp: Res<CrispUpscaling>,
+    mut images: ResMut<Assets<Image>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<&mut Camera, (With<CameraZoom>, Without<IsDefaultUiCamera>)>,
+    mut blit_camera_query: Query<&mut Camera, With<IsDefaultUiCamera>>,
+    mut blit_query: Query<(&mut Sprite, &mut Handle<Image>, &mut Visibility), With<RenderBlit>>,
+) {
+    let (Ok(window), Ok(mut blit_camera)) = (
+        window_query.get_single(),
+        blit_camera_query.get_single_mut(),
+    ) else {
+        return;
+    };
+    let Some(viewport) = blit_camera.viewport.clone() else {
+        return;
+    };
+    let scaled = render_scale.0 < 1.0;
+    let image_size = (viewport.physical_size.as_vec2() * render_scale.0.max(0.1))
+        .as_uvec2()
+        .max(UVec2::ONE);
+    let image = scaled.then(|| images.add(render_image(image_size, crisp.0)));
+
+    for mut camera in &mut camera_query {
+        match &image {
+            Some(image) => {
+                camera.target = RenderTarget::Image(image.clone());
+                camera.viewport = None;
+            }
+            None => {
+                camera.target = RenderTarget::default();
+                camera.viewport = Some(viewport.clone());
+            }
+        }
+    }
+
+    // the game camera no longer clears the window around the viewport while scaled down
+    blit_camera.clear_color = if scaled {
+        ClearColorConfig::Default
+    } else {
+        ClearColorConfig::None
+    };
+    for (mut sprite, mut texture, mut visibility) in &mut blit_query {
+        match &image {
+            Some(image) => {
+                *texture = image.clone();
+                sprite.custom_size = Some(viewport.physical_size.as_vec2() / window.scale_factor());
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+/// A blank image the game camera can render into, sampled as [`CrispUpscaling`] asks.
+fn render_image(size: UVec2, crisp: bool) -> Image {
+    let size = Extent3d {
+        width: size.x,
+        height: size.y,
+        depth_or_array_layers: 1,
+    };
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: Some("scaled playfield"),
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::bevy_default(),
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        sampler: if crisp {
+            ImageSampler::nearest()
+        } else {
+            ImageSampler::linear()
+        },
+        ..default()
+    };
+    // fills the image with zeroes
+    image.resize(size);
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{time::TimeUpdateStrategy, window::WindowResolution};
+
+    use super::*;
+
+    /// Resizes the primary window and sends the matching [`WindowResized`] event.
+    fn resize_window(app: &mut App, window: Entity, width: f32, height: f32) {
+        let mut window_ref = app.world_mut().get_mut::<Window>(window).unwrap();
+        window_ref.resolution.set(width, height);
+        app.world_mut().send_event(WindowResized {
+            window,
+            width,
+            height,
+        });
+    }
+
+    fn viewport(app: &mut App) -> Viewport {
+        let mut query = app
+            .world_mut()
+            .query_filtered::<&Camera, With<IsDefaultUiCamera>>();
+        query.single(app.world()).viewport.clone().unwrap()
+    }
+
+    #[test]
+    fn viewport_follows_window_resize() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_event::<WindowResized>();
+        app.init_resource::<CameraFit>();
+        app.add_systems(Update, apply_camera_fit.run_if(on_event::<WindowResized>()));
+
+        let window = app
+            .world_mut()
+            .spawn((
+                Window {
+                    resolution: WindowResolution::new(1200.0, 640.0)
+                        .with_scale_factor_override(1.0),
+                    ..default()
+                },
+                PrimaryWindow,
+            ))
+            .id();
+        app.world_mut()
+            .spawn((OrthographicProjection::default(), CameraZoom::default()));
+        app.world_mut()
+            .spawn((Camera::default(), IsDefaultUiCamera));
+
+        resize_window(&mut app, window, 1200.0, 640.0);
+        app.update();
+        assert_eq!(viewport(&mut app).physical_size, UVec2::new(1200, 640));
+        assert_eq!(viewport(&mut app).physical_position, UVec2::ZERO);
+
+        // a wider window gets bars on the sides, with the playfield centred
+        resize_window(&mut app, window, 1600.0, 640.0);
+        app.update();
+        assert_eq!(viewport(&mut app).physical_size, UVec2::new(1200, 640));
+        assert_eq!(viewport(&mut app).physical_position, UVec2::new(200, 0));
+
+        // a narrower window still shows the full height of the playfield
+        resize_window(&mut app, window, 600.0, 640.0);
+        app.update();
+        assert_eq!(viewport(&mut app).physical_size, UVec2::new(600, 640));
+        assert_eq!(viewport(&mut app).physical_position, UVec2::ZERO);
+    }
+
+    #[test]
+    fn scrolling_zooms_within_limits() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        let step = Duration::from_millis(50);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(step));
+        app.add_event::<MouseWheel>();
+        app.add_systems(Update, camera_zoom_system);
+        let camera = app
+            .world_mut()
+            .spawn((
+                OrthographicProjection::default(),
+                CameraZoom::default(),
+                CameraPunch::default(),
+            ))
+            .id();
+        let scroll = |app: &mut App, lines: f32| {
+            app.world_mut().send_event(MouseWheel {
+                unit: MouseScrollUnit::Line,
+                x: 0.0,
+                y: lines,
+                window: Entity::PLACEHOLDER,
+            });
+            app.update();
+        };
+        let target = |app: &App| app.world().get::<CameraZoom>(camera).unwrap().target;
+
+        // the first update only starts the clock
+        app.update();
+
+        // scrolling up zooms in, easing the projection halfway to the target in 50 ms
+        scroll(&mut app, 2.0);
+        assert!((target(&app) - 0.8).abs() < 1e-5);
+        let scale = app
+            .world()
+            .get::<OrthographicProjection>(camera)
+            .unwrap()
+            .scale;
+        assert!((scale - 0.9).abs() < 1e-5);
+
+        // however far the wheel turns, the zoom stays within its limits
+        scroll(&mut app, 100.0);
+        assert_eq!(target(&app), MIN_ZOOM);
+        scroll(&mut app, -100.0);
+        assert_eq!(target(&app), MAX_ZOOM);
+    }
 }
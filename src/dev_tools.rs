@@ -1,10 +1,328 @@
 //! Development tools for the game. This plugin is only enabled in dev builds.
 
-use bevy::{dev_tools::states::log_transitions, prelude::*};
+use bevy::{dev_tools::states::log_transitions, prelude::*, window::PrimaryWindow};
 
-use crate::screen::Screen;
+use crate::{
+    camera::{window_to_world, CameraZoom, BOUNDS},
+    game::{
+        enemy::Enemy,
+        input::{Action, ActionState},
+        spawn::player::Player,
+    },
+    screen::Screen,
+};
 
 pub(super) fn plugin(app: &mut App) {
     // Print state transitions in dev builds
     app.add_systems(Update, log_transitions::<Screen>);
+
+    // Toggle a world-space grid over the playfield
+    app.init_resource::<ShowWorldGrid>();
+    app.add_systems(Startup, spawn_origin_label);
+    app.add_systems(Update, (toggle_world_grid, draw_world_grid));
+
+    // Toggle a heat map of enemy density, binned into the grid cells
+    app.init_resource::<ShowDensityMap>();
+    app.add_systems(Startup, spawn_density_cells);
+    app.add_systems(Update, (toggle_density_map, update_density_map).chain());
+
+    // Middle-click an enemy to show its steering vectors
+    app.init_resource::<SelectedEnemy>();
+    app.add_systems(Startup, spawn_selected_enemy_label);
+    app.add_systems(Update, (select_enemy, draw_selected_enemy_debug).chain());
+}
+
+/// Debug toggle for the world-space coordinate grid.
+#[derive(Resource, Default)]
+struct ShowWorldGrid(bool);
+
+/// Marks the text label drawn at the world origin while the grid is shown.
+#[derive(Component)]
+struct OriginLabel;
+
+/// Distance between grid lines, in world units.
+const GRID_SPACING: f32 = 100.0;
+
+/// Debug toggle for the enemy density heat map.
+#[derive(Resource, Default)]
+struct ShowDensityMap(bool);
+
+/// One cell of the density heat map, covering the grid cell at `index`.
+#[derive(Component)]
+struct DensityCell {
+    index: UVec2,
+}
+
+/// The enemy whose steering vectors are drawn, picked by middle-clicking near it.
+#[derive(Resource, Default)]
+struct SelectedEnemy(Option<Entity>);
+
+/// Marks the text label that follows the [`SelectedEnemy`].
+#[derive(Component)]
+struct SelectedEnemyLabel;
+
+/// Clicks further than this from every enemy clear the selection.
+const SELECT_RADIUS: f32 = 60.0;
+
+/// Number of enemies in one cell that shows at full brightness.
+const DENSITY_CELL_MAX: f32 = 5.0;
+
+/// Number of grid cells across the bounds in each axis.
+fn density_grid_size() -> UVec2 {
+    (BOUNDS / GRID_SPACING).ceil().as_uvec2()
+}
+
+/// The grid cell containing `position`, or `None` outside the bounds.
+fn density_cell_index(position: Vec2) -> Option<UVec2> {
+    let cell = ((position + BOUNDS / 2.0) / GRID_SPACING).floor();
+    let size = density_grid_size();
+    (cell.cmpge(Vec2::ZERO).all() && cell.cmplt(size.as_vec2()).all()).then(|| cell.as_uvec2())
+}
+
+fn spawn_origin_label(mut commands: Commands) {
+    // Pushed behind the ships.
+    commands.spawn((
+        Name::new("Origin Label"),
+        Text2dBundle {
+            text: Text::from_section("(0, 0)", TextStyle::default()),
+            transform: Transform::from_xyz(30.0, -15.0, -1.0),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        OriginLabel,
+    ));
+}
+
+fn toggle_world_grid(
+    actions: Res<ActionState>,
+    mut show_grid: ResMut<ShowWorldGrid>,
+    mut label_query: Query<&mut Visibility, With<OriginLabel>>,
+) {
+    if actions.just_pressed(Action::ToggleWorldGrid) {
+        show_grid.0 = !show_grid.0;
+        for mut visibility in &mut label_query {
+            *visibility = if show_grid.0 {
+                Visibility::Visible
+            } else {
+                Visibility::Hidden
+            };
+        }
+    }
+}
+
+/// Draws grid lines every [`GRID_SPACING`] units inside [`BOUNDS`] and outlines the bounds themselves.
+fn draw_world_grid(show_grid: Res<ShowWorldGrid>, mut gizmos: Gizmos) {
+    if !show_grid.0 {
+        return;
+    }
+
+    let half_bounds = BOUNDS / 2.0;
+    let line_color = Color::srgba(1.0, 1.0, 1.0, 0.1);
+    let axis_color = Color::srgba(1.0, 1.0, 1.0, 0.3);
+
+    // Start from the first multiple of the spacing inside the bounds so lines pass through the origin.
+    let mut x = (-half_bounds.x / GRID_SPACING).ceil() * GRID_SPACING;
+    while x <= half_bounds.x {
+        let color = if x == 0.0 { axis_color } else { line_color };
+        gizmos.line_2d(
+            Vec2::new(x, -half_bounds.y),
+            Vec2::new(x, half_bounds.y),
+            color,
+        );
+        x += GRID_SPACING;
+    }
+
+    let mut y = (-half_bounds.y / GRID_SPACING).ceil() * GRID_SPACING;
+    while y <= half_bounds.y {
+        let color = if y == 0.0 { axis_color } else { line_color };
+        gizmos.line_2d(
+            Vec2::new(-half_bounds.x, y),
+            Vec2::new(half_bounds.x, y),
+            color,
+        );
+        y += GRID_SPACING;
+    }
+
+    gizmos.rect_2d(Vec2::ZERO, 0.0, BOUNDS, Color::srgb(1.0, 0.3, 0.3));
+}
+
+fn spawn_density_cells(mut commands: Commands) {
+    let size = density_grid_size();
+    for x in 0..size.x {
+        for y in 0..size.y {
+            let index = UVec2::new(x, y);
+            let center = -BOUNDS / 2.0 + (index.as_vec2() + 0.5) * GRID_SPACING;
+            // Pushed behind the ships.
+            commands.spawn((
+                Name::new("Density Cell"),
+                SpriteBundle {
+                    sprite: Sprite {
+                        custom_size: Some(Vec2::splat(GRID_SPACING)),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(center.extend(-2.0)),
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+                DensityCell { index },
+            ));
+        }
+    }
+}
+
+fn toggle_density_map(
+    actions: Res<ActionState>,
+    mut show_density: ResMut<ShowDensityMap>,
+    mut cell_query: Query<&mut Visibility, With<DensityCell>>,
+) {
+    if actions.just_pressed(Action::ToggleDensityMap) {
+        show_density.0 = !show_density.0;
+        for mut visibility in &mut cell_query {
+            *visibility = if show_density.0 {
+                Visibility::Visible
+            } else {
+                Visibility::Hidden
+            };
+        }
+    }
+}
+
+/// Counts the enemies in each grid cell and tints the cells from transparent blue to red.
+fn update_density_map(
+    show_density: Res<ShowDensityMap>,
+    enemy_query: Query<&Transform, With<Enemy>>,
+    mut cell_query: Query<(&DensityCell, &mut Sprite)>,
+) {
+    if !show_density.0 {
+        return;
+    }
+
+    let size = density_grid_size();
+    let mut counts = vec![0u32; (size.x * size.y) as usize];
+    for transform in &enemy_query {
+        if let Some(index) = density_cell_index(transform.translation.xy()) {
+            counts[(index.y * size.x + index.x) as usize] += 1;
+        }
+    }
+
+    for (cell, mut sprite) in &mut cell_query {
+        let count = counts[(cell.index.y * size.x + cell.index.x) as usize];
+        let heat = (count as f32 / DENSITY_CELL_MAX).min(1.0);
+        sprite.color = if count == 0 {
+            Color::NONE
+        } else {
+            Color::srgba(heat, 0.2, 1.0 - heat, 0.15 + 0.35 * heat)
+        };
+    }
+}
+
+fn spawn_selected_enemy_label(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Selected Enemy Label"),
+        Text2dBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font_size: 16.0,
+                    ..default()
+                },
+            ),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        SelectedEnemyLabel,
+    ));
+}
+
+/// Selects the enemy closest to the cursor on click, or clears the selection if none is close.
+fn select_enemy(
+    actions: Res<ActionState>,
+    mut selected: ResMut<SelectedEnemy>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<CameraZoom>>,
+    blit_camera_query: Query<&Camera, With<IsDefaultUiCamera>>,
+    enemy_query: Query<(Entity, &Transform), With<Enemy>>,
+) {
+    if !actions.just_pressed(Action::SelectEnemy) {
+        return;
+    }
+    let (Ok(window), Ok((camera, camera_transform)), Ok(blit_camera)) = (
+        window_query.get_single(),
+        camera_query.get_single(),
+        blit_camera_query.get_single(),
+    ) else {
+        return;
+    };
+    let Some(cursor) = window
+        .cursor_position()
+        .and_then(|cursor| window_to_world(cursor, camera, camera_transform, blit_camera))
+    else {
+        return;
+    };
+
+    selected.0 = enemy_query
+        .iter()
+        .map(|(entity, transform)| (entity, transform.translation.xy().distance(cursor)))
+        .filter(|(_, distance)| *distance < SELECT_RADIUS)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity);
+}
+
+/// Draws the selected enemy's facing and the vector to the player, and labels it with the
+/// distance and the dot products the steering systems use.
+fn draw_selected_enemy_debug(
+    mut gizmos: Gizmos,
+    mut selected: ResMut<SelectedEnemy>,
+    enemy_query: Query<&Transform, With<Enemy>>,
+    player_query: Query<&Transform, With<Player>>,
+    mut label_query: Query<
+        (&mut Text, &mut Transform, &mut Visibility),
+        (With<SelectedEnemyLabel>, Without<Enemy>, Without<Player>),
+    >,
+) {
+    let Ok((mut text, mut label_transform, mut visibility)) = label_query.get_single_mut() else {
+        return;
+    };
+
+    // clear the selection once the enemy is killed or the run ends
+    let enemy_transform = selected.0.and_then(|entity| enemy_query.get(entity).ok());
+    let (Some(enemy_transform), Ok(player_transform)) =
+        (enemy_transform, player_query.get_single())
+    else {
+        selected.0 = None;
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    let position = enemy_transform.translation.xy();
+    let forward = (enemy_transform.rotation * Vec3::Y).xy();
+    let right = (enemy_transform.rotation * Vec3::X).xy();
+    let to_player = player_transform.translation.xy() - position;
+    let direction = to_player.normalize_or_zero();
+
+    gizmos.circle_2d(position, 30.0, Color::srgb(1.0, 1.0, 0.3));
+    gizmos.arrow_2d(
+        position,
+        position + forward * 80.0,
+        Color::srgb(0.3, 1.0, 0.3),
+    );
+    gizmos.line_2d(
+        position,
+        position + to_player,
+        Color::srgba(0.3, 0.6, 1.0, 0.6),
+    );
+    gizmos.line_2d(
+        position,
+        position + right * 40.0,
+        Color::srgb(1.0, 0.3, 0.3),
+    );
+
+    text.sections[0].value = format!(
+        "distance: {:.0}\nforward . player: {:.2}\nright . player: {:.2}",
+        to_player.length(),
+        forward.dot(direction),
+        right.dot(direction),
+    );
+    label_transform.translation = (position + Vec2::new(0.0, 60.0)).extend(10.0);
+    *visibility = Visibility::Visible;
 }
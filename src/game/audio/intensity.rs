@@ -0,0 +1,73 @@
+//! Music that swells as enemies crowd in on the player.
+//! There is a single soundtrack per screen, so intensity lifts its volume and tempo
+//! rather than crossfading between layers.
+
+use bevy::{audio::AudioSinkPlayback, prelude::*};
+
+use super::soundtrack::SoundtrackMarker;
+use crate::game::{enemy::Enemy, spawn::player::Player};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<MusicIntensity>();
+    app.init_resource::<MusicIntensity>();
+    app.add_systems(
+        Update,
+        (update_music_intensity, apply_music_intensity).chain(),
+    );
+}
+
+/// Enemies alive at which the count alone drives the music to full intensity.
+const CROWDED_ENEMY_COUNT: f32 = 20.0;
+/// An enemy this close to the player drives the music to full intensity, fading out
+/// to none at `CALM_DISTANCE`.
+const DANGER_DISTANCE: f32 = 80.0;
+const CALM_DISTANCE: f32 = 400.0;
+/// How quickly the intensity follows the action, per second.
+const INTENSITY_SMOOTHING: f32 = 1.5;
+/// Soundtrack volume at no and full intensity.
+const CALM_VOLUME: f32 = 0.7;
+const INTENSE_VOLUME: f32 = 1.0;
+/// Extra playback speed at full intensity.
+const INTENSE_SPEED_UP: f32 = 0.08;
+
+/// How intense the music currently is, from 0 (calm) to 1 (intense).
+/// Eases toward a target from the live enemy count and the nearest enemy's distance.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct MusicIntensity(pub f32);
+
+/// Outside of a run there are no enemies, so the music settles back to calm.
+fn update_music_intensity(
+    time: Res<Time<Real>>,
+    mut intensity: ResMut<MusicIntensity>,
+    player_query: Query<&Transform, With<Player>>,
+    enemy_query: Query<&Transform, With<Enemy>>,
+) {
+    let crowd = (enemy_query.iter().len() as f32 / CROWDED_ENEMY_COUNT).min(1.0);
+    let proximity = player_query.get_single().ok().map_or(0.0, |player| {
+        let position = player.translation.xy();
+        enemy_query
+            .iter()
+            .map(|enemy| enemy.translation.xy().distance(position))
+            .reduce(f32::min)
+            .map_or(0.0, |nearest| {
+                1.0 - ((nearest - DANGER_DISTANCE) / (CALM_DISTANCE - DANGER_DISTANCE))
+                    .clamp(0.0, 1.0)
+            })
+    });
+    let target = crowd.max(proximity);
+
+    let smoothing = (INTENSITY_SMOOTHING * time.delta_seconds()).min(1.0);
+    intensity.0 += (target - intensity.0) * smoothing;
+}
+
+/// Runs every frame, since the soundtrack's sink only shows up once its audio has loaded.
+fn apply_music_intensity(
+    intensity: Res<MusicIntensity>,
+    sink_query: Query<&AudioSink, With<SoundtrackMarker>>,
+) {
+    for sink in &sink_query {
+        sink.set_volume(CALM_VOLUME + (INTENSE_VOLUME - CALM_VOLUME) * intensity.0);
+        sink.set_speed(1.0 + INTENSE_SPEED_UP * intensity.0);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod intensity;
 pub mod sfx;
 pub mod soundtrack;
 
@@ -5,6 +6,7 @@ use bevy::prelude::*;
 use sfx::Sfx;
 
 pub fn plugin(app: &mut App) {
+    app.add_plugins(intensity::plugin);
     app.add_systems(Update, button_interaction_sfx);
 
     app.observe(soundtrack::play_soundtrack);
@@ -1,5 +1,5 @@
 use bevy::{audio::PlaybackMode, prelude::*};
-use rand::prelude::SliceRandom;
+use rand::prelude::*;
 
 pub(super) fn play_sfx(
     trigger: Trigger<Sfx>,
@@ -7,14 +7,16 @@ pub(super) fn play_sfx(
     asset_server: Res<AssetServer>,
 ) {
     let event = trigger.event();
-    let path = match event {
-        Sfx::ButtonHover => "audio/sfx/button_hover.ogg",
-        Sfx::ButtonPress => "audio/sfx/button_press.ogg",
-        Sfx::Step => random_step(),
-    };
-    let source = asset_server.load::<AudioSource>(path);
+    let mut rng = thread_rng();
+    let path = event.variations().choose(&mut rng).unwrap();
+    let source = asset_server.load::<AudioSource>(*path);
+
+    // nudge the pitch up or down a little so repeated sounds don't feel mechanical
+    let pitch_variation = event.pitch_variation();
+    let speed = 1.0 + rng.gen_range(-pitch_variation..=pitch_variation);
     let settings = PlaybackSettings {
         mode: PlaybackMode::Despawn,
+        speed,
         ..default()
     };
     commands.spawn(AudioSourceBundle { source, settings });
@@ -28,13 +30,26 @@ pub enum Sfx {
     Step,
 }
 
-fn random_step() -> &'static str {
-    [
-        "audio/sfx/step1.ogg",
-        "audio/sfx/step2.ogg",
-        "audio/sfx/step3.ogg",
-        "audio/sfx/step4.ogg",
-    ]
-    .choose(&mut rand::thread_rng())
-    .unwrap()
+impl Sfx {
+    /// The sounds this effect picks from at random each time it plays.
+    fn variations(&self) -> &'static [&'static str] {
+        match self {
+            Self::ButtonHover => &["audio/sfx/button_hover.ogg"],
+            Self::ButtonPress => &["audio/sfx/button_press.ogg"],
+            Self::Step => &[
+                "audio/sfx/step1.ogg",
+                "audio/sfx/step2.ogg",
+                "audio/sfx/step3.ogg",
+                "audio/sfx/step4.ogg",
+            ],
+        }
+    }
+
+    /// How far the playback speed, and with it the pitch, may stray from normal.
+    fn pitch_variation(&self) -> f32 {
+        match self {
+            Self::ButtonHover | Self::ButtonPress => 0.0,
+            Self::Step => 0.1,
+        }
+    }
 }
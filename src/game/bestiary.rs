@@ -0,0 +1,183 @@
+//! A record of every kind of enemy the player has encountered, kept between sessions and
+//! listed on the bestiary screen.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    combat::EnemyKilled,
+    enemy::{Enemy, EnemyKind},
+};
+use crate::screen::Screen;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Bestiary>();
+    app.init_resource::<Bestiary>();
+    // The web build has no file system to keep the bestiary in, so there it only lasts
+    // the session.
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        app.add_systems(Startup, persistence::load_bestiary);
+        app.add_systems(OnExit(Screen::Playing), persistence::save_bestiary);
+    }
+    app.add_systems(
+        Update,
+        discover_enemies.run_if(in_state(Screen::Playing).and_then(any_with_component::<Enemy>)),
+    );
+    app.observe(count_bestiary_kill);
+}
+
+/// One kind of enemy. Its stats come from [`EnemyKind`], so they are the base stats of the
+/// kind rather than those of any one enemy.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Reflect)]
+pub struct BestiaryEntry {
+    pub kind: EnemyKind,
+    /// Asset path of its sprite, if it has one.
+    pub texture: Option<String>,
+    /// Kills across every run.
+    pub kills: u32,
+}
+
+/// Every kind of enemy encountered so far, in the order they first appeared.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(Resource)]
+pub struct Bestiary {
+    pub entries: Vec<BestiaryEntry>,
+}
+
+impl Bestiary {
+    fn entry_mut(&mut self, kind: EnemyKind) -> Option<&mut BestiaryEntry> {
+        self.entries.iter_mut().find(|entry| entry.kind == kind)
+    }
+}
+
+/// Saving and loading the bestiary as RON, next to the game.
+#[cfg(not(target_arch = "wasm32"))]
+mod persistence {
+    use std::{fs, io};
+
+    use bevy::prelude::*;
+    use thiserror::Error;
+
+    use super::Bestiary;
+
+    /// Where the bestiary is saved between sessions.
+    const BESTIARY_PATH: &str = "bestiary.ron";
+
+    #[derive(Debug, Error)]
+    enum BestiaryError {
+        #[error(transparent)]
+        Io(#[from] io::Error),
+        #[error(transparent)]
+        Parse(#[from] ron::error::SpannedError),
+        #[error(transparent)]
+        Write(#[from] ron::Error),
+    }
+
+    impl Bestiary {
+        fn save(&self) -> Result<(), BestiaryError> {
+            let contents = ron::ser::to_string_pretty(self, default())?;
+            fs::write(BESTIARY_PATH, contents)?;
+            Ok(())
+        }
+
+        fn load() -> Result<Self, BestiaryError> {
+            Ok(ron::from_str(&fs::read_to_string(BESTIARY_PATH)?)?)
+        }
+    }
+
+    pub(super) fn load_bestiary(mut commands: Commands) {
+        let bestiary = match Bestiary::load() {
+            Ok(bestiary) => bestiary,
+            Err(BestiaryError::Io(error)) if error.kind() == io::ErrorKind::NotFound => default(),
+            Err(error) => {
+                warn!("Failed to load the bestiary: {error}");
+                default()
+            }
+        };
+        commands.insert_resource(bestiary);
+    }
+
+    pub(super) fn save_bestiary(bestiary: Res<Bestiary>) {
+        if let Err(error) = bestiary.save() {
+            warn!("Failed to save the bestiary: {error}");
+        }
+    }
+}
+
+/// Adds an entry the first time a kind of enemy appears.
+fn discover_enemies(
+    mut bestiary: ResMut<Bestiary>,
+    enemy_query: Query<(&EnemyKind, Option<&Handle<Image>>), Added<Enemy>>,
+) {
+    for (&kind, texture) in &enemy_query {
+        if bestiary.entry_mut(kind).is_some() {
+            continue;
+        }
+
+        info!("New enemy encountered: {}", kind.name());
+        bestiary.entries.push(BestiaryEntry {
+            kind,
+            texture: texture
+                .and_then(Handle::path)
+                .map(|path| path.path().to_string_lossy().into_owned()),
+            kills: 0,
+        });
+    }
+}
+
+fn count_bestiary_kill(
+    trigger: Trigger<EnemyKilled>,
+    mut bestiary: ResMut<Bestiary>,
+    kind_query: Query<&EnemyKind>,
+) {
+    let Ok(&kind) = kind_query.get(trigger.entity()) else {
+        return;
+    };
+    if let Some(entry) = bestiary.entry_mut(kind) {
+        entry.kills += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enemies_of_one_kind_share_an_entry() {
+        let mut app = App::new();
+        app.init_resource::<Bestiary>();
+        app.add_systems(Update, discover_enemies);
+        app.observe(count_bestiary_kill);
+
+        let drones = [(); 2].map(|()| {
+            app.world_mut()
+                .spawn((Enemy, EnemyKind::Drone, Handle::<Image>::default()))
+                .id()
+        });
+        app.world_mut().spawn((Enemy, EnemyKind::Snapper));
+        app.update();
+        for drone in drones {
+            app.world_mut().trigger_targets(EnemyKilled, drone);
+        }
+
+        let bestiary = app.world().resource::<Bestiary>();
+        let kinds: Vec<_> = bestiary.entries.iter().map(|entry| entry.kind).collect();
+        assert_eq!(kinds, [EnemyKind::Drone, EnemyKind::Snapper]);
+        assert_eq!(bestiary.entries[0].kills, 2);
+    }
+
+    #[test]
+    fn bestiary_round_trips_through_ron() {
+        let bestiary = Bestiary {
+            entries: vec![BestiaryEntry {
+                kind: EnemyKind::Juggernaut,
+                texture: Some("enemy_B.png".to_string()),
+                kills: 3,
+            }],
+        };
+
+        let saved = ron::ser::to_string_pretty(&bestiary, default()).unwrap();
+        assert_eq!(ron::from_str::<Bestiary>(&saved).unwrap(), bestiary);
+    }
+}
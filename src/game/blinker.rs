@@ -0,0 +1,202 @@
+//! Blinker enemies that stay put and teleport toward the player every few seconds.
+
+use bevy::prelude::*;
+
+use super::{
+    combat::{Health, Team},
+    enemy::{Enemy, EnemyKind, RotateToPlayer},
+    lifetime::Lifetime,
+    single_or_skip,
+    spawn::player::Player,
+};
+use crate::{camera::BOUNDS, screen::Screen, settings::PerformanceMode, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Blinker, BlinkFlash)>();
+    app.add_systems(OnEnter(Screen::Playing), spawn_blinkers);
+    app.add_systems(
+        Update,
+        (
+            tick_blinkers.in_set(AppSet::TickTimers),
+            (blink_system, telegraph_blink_system, fade_blink_flashes).in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+/// Blinkers never land closer than this to the player.
+const BLINK_MIN_GAP: f32 = 60.0;
+const BLINK_COLOR: Color = Color::srgb(0.9, 0.4, 1.0);
+
+/// An enemy that teleports `jump_distance` toward the player whenever its cooldown fires.
+/// The blink is telegraphed for a moment before it happens.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Blinker {
+    pub cooldown: Timer,
+    pub jump_distance: f32,
+    /// Runs between the cooldown firing and the actual blink.
+    pub telegraph: Timer,
+    telegraphing: bool,
+}
+
+impl Blinker {
+    pub fn new(cooldown_secs: f32, jump_distance: f32) -> Self {
+        Self {
+            cooldown: Timer::from_seconds(cooldown_secs, TimerMode::Once),
+            jump_distance,
+            telegraph: Timer::from_seconds(0.5, TimerMode::Once),
+            telegraphing: false,
+        }
+    }
+
+    /// Where a blinker at `position` would land when blinking toward `target`.
+    fn destination(&self, position: Vec2, target: Vec2) -> Vec2 {
+        let to_target = target - position;
+        let jump = self
+            .jump_distance
+            .min(to_target.length() - BLINK_MIN_GAP)
+            .max(0.0);
+        let destination = position + to_target.normalize_or_zero() * jump;
+        destination.clamp(-BOUNDS / 2.0, BOUNDS / 2.0)
+    }
+}
+
+/// A short flash left where a [`Blinker`] disappeared and reappeared.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct BlinkFlash;
+
+fn spawn_blinkers(mut commands: Commands, asset_server: Res<AssetServer>) {
+    // one blinker in each bottom corner
+    for x in [-1.0, 1.0] {
+        let position = Vec2::new(x * BOUNDS.x * 0.4, -BOUNDS.y * 0.35);
+
+        commands.spawn((
+            Name::new("Blinker"),
+            SpriteBundle {
+                sprite: Sprite {
+                    color: BLINK_COLOR,
+                    ..default()
+                },
+                texture: asset_server.load("enemy_A.png"),
+                transform: Transform::from_translation(position.extend(0.0)),
+                ..default()
+            },
+            Blinker::new(3.0, 220.0),
+            RotateToPlayer {
+                rotation_speed: f32::to_radians(180.0), // degrees per second
+            },
+            Enemy,
+            EnemyKind::Blinker,
+            Team::Hostile,
+            Health {
+                value: EnemyKind::Blinker.base_health(),
+            },
+            StateScoped(Screen::Playing),
+        ));
+    }
+}
+
+fn tick_blinkers(time: Res<Time>, mut blinker_query: Query<&mut Blinker>) {
+    for mut blinker in &mut blinker_query {
+        if blinker.telegraphing {
+            blinker.telegraph.tick(time.delta());
+        } else {
+            blinker.cooldown.tick(time.delta());
+        }
+    }
+}
+
+/// Starts the telegraph when a blinker's cooldown fires, and teleports it once the telegraph ends.
+fn blink_system(
+    mut commands: Commands,
+    performance: Res<PerformanceMode>,
+    player_query: Query<&Transform, With<Player>>,
+    mut blinker_query: Query<(&mut Blinker, &mut Transform, &mut Sprite), Without<Player>>,
+) {
+    let Some(player_transform) = single_or_skip(&player_query) else {
+        return;
+    };
+    let player_translation = player_transform.translation.xy();
+
+    for (mut blinker, mut transform, mut sprite) in &mut blinker_query {
+        if !blinker.telegraphing && blinker.cooldown.just_finished() {
+            blinker.telegraphing = true;
+            blinker.telegraph.reset();
+        } else if blinker.telegraphing && blinker.telegraph.just_finished() {
+            let origin = transform.translation.xy();
+            let destination = blinker.destination(origin, player_translation);
+            transform.translation = destination.extend(transform.translation.z);
+
+            if !performance.0 {
+                spawn_blink_flash(&mut commands, origin);
+                spawn_blink_flash(&mut commands, destination);
+            }
+
+            blinker.telegraphing = false;
+            blinker.cooldown.reset();
+            sprite.color.set_alpha(1.0);
+        }
+    }
+}
+
+/// Flickers telegraphing blinkers and marks where they are about to land.
+fn telegraph_blink_system(
+    mut gizmos: Gizmos,
+    player_query: Query<&Transform, With<Player>>,
+    mut blinker_query: Query<(&Blinker, &Transform, &mut Sprite), Without<Player>>,
+) {
+    let Some(player_transform) = single_or_skip(&player_query) else {
+        return;
+    };
+    let player_translation = player_transform.translation.xy();
+
+    for (blinker, transform, mut sprite) in &mut blinker_query {
+        if !blinker.telegraphing {
+            continue;
+        }
+
+        // flicker faster as the blink approaches
+        let fraction = blinker.telegraph.fraction();
+        let flicker = (fraction * fraction * 40.0).sin() * 0.5 + 0.5;
+        sprite.color.set_alpha(0.3 + 0.7 * flicker);
+
+        let destination = blinker.destination(transform.translation.xy(), player_translation);
+        gizmos.circle_2d(
+            destination,
+            30.0 * (1.0 - fraction) + 10.0,
+            BLINK_COLOR.with_alpha(fraction),
+        );
+    }
+}
+
+fn spawn_blink_flash(commands: &mut Commands, position: Vec2) {
+    commands.spawn((
+        Name::new("Blink Flash"),
+        SpriteBundle {
+            sprite: Sprite {
+                color: BLINK_COLOR,
+                custom_size: Some(Vec2::splat(40.0)),
+                ..default()
+            },
+            // draw the flash on top of the ships
+            transform: Transform::from_translation(position.extend(1.0)),
+            ..default()
+        },
+        BlinkFlash,
+        Lifetime::from_seconds(0.25),
+        StateScoped(Screen::Playing),
+    ));
+}
+
+/// Grows and fades blink flashes over their lifetime.
+fn fade_blink_flashes(
+    mut flash_query: Query<(&Lifetime, &mut Sprite, &mut Transform), With<BlinkFlash>>,
+) {
+    for (lifetime, mut sprite, mut transform) in &mut flash_query {
+        let fraction = lifetime.fraction();
+        sprite.color.set_alpha(1.0 - fraction);
+        transform.scale = Vec3::splat(1.0 + fraction);
+    }
+}
@@ -0,0 +1,199 @@
+//! Charger enemies that lock onto the player and fire a heavy beam after a telegraph.
+
+use bevy::prelude::*;
+
+use super::{
+    combat::{
+        apply_damage, distance_to_segment, DamageType, FriendlyFire, Health, Invulnerable,
+        Resistances, Team,
+    },
+    enemy::{Enemy, EnemyKind},
+    lifetime::Lifetime,
+    single_or_skip_mut,
+    spawn::player::Player,
+};
+use crate::{camera::BOUNDS, screen::Screen, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Charger, ChargerBeam)>();
+    app.add_systems(OnEnter(Screen::Playing), spawn_chargers);
+    app.add_systems(
+        Update,
+        (
+            tick_chargers.in_set(AppSet::TickTimers),
+            (charger_system, draw_charger_telegraphs, fade_charger_beams).in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+const BEAM_COLOR: Color = Color::srgb(1.0, 0.3, 0.6);
+
+/// An enemy that picks the player's position, telegraphs a line through it while `charge` runs,
+/// and then fires a beam along that line. The aim never follows the player once it is locked.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Charger {
+    /// Time between the end of one beam and locking on for the next.
+    pub cooldown: Timer,
+    /// How long the telegraph is shown before the beam fires.
+    pub charge: Timer,
+    pub damage: f32,
+    pub range: f32,
+    /// The player is hit if they are closer than this to the beam.
+    pub half_width: f32,
+    /// Direction of the locked-on beam while charging.
+    aim: Option<Vec2>,
+}
+
+impl Charger {
+    /// Start and end points of a beam fired from `position` along `aim`.
+    fn segment(&self, position: Vec2, aim: Vec2) -> (Vec2, Vec2) {
+        (position, position + aim * self.range)
+    }
+}
+
+/// The fired beam, fading out over a moment. Purely visual; the hit is resolved when it spawns.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct ChargerBeam;
+
+fn spawn_chargers(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        Name::new("Charger"),
+        SpriteBundle {
+            sprite: Sprite {
+                color: BEAM_COLOR,
+                ..default()
+            },
+            texture: asset_server.load("enemy_B.png"),
+            transform: Transform::from_xyz(0.0, BOUNDS.y * 0.4, 0.0)
+                .with_rotation(Quat::from_rotation_z(std::f32::consts::PI)),
+            ..default()
+        },
+        Charger {
+            cooldown: Timer::from_seconds(3.0, TimerMode::Once),
+            charge: Timer::from_seconds(1.0, TimerMode::Once),
+            damage: 35.0,
+            range: 1400.0,
+            half_width: 18.0,
+            aim: None,
+        },
+        Enemy,
+        EnemyKind::Charger,
+        Team::Hostile,
+        Health {
+            value: EnemyKind::Charger.base_health(),
+        },
+        StateScoped(Screen::Playing),
+    ));
+}
+
+fn tick_chargers(time: Res<Time>, mut charger_query: Query<&mut Charger>) {
+    for mut charger in &mut charger_query {
+        if charger.aim.is_some() {
+            charger.charge.tick(time.delta());
+        } else {
+            charger.cooldown.tick(time.delta());
+        }
+    }
+}
+
+/// Locks chargers onto the player when their cooldown fires,
+/// and fires the beam at the locked aim once the charge is complete.
+fn charger_system(
+    mut commands: Commands,
+    friendly_fire: Res<FriendlyFire>,
+    mut player_query: Query<
+        (
+            Entity,
+            &mut Health,
+            &Transform,
+            &Team,
+            Option<&Resistances>,
+            Has<Invulnerable>,
+        ),
+        With<Player>,
+    >,
+    mut charger_query: Query<(&mut Charger, &mut Transform, &Team), Without<Player>>,
+) {
+    let Some((player, mut health, player_transform, player_team, resistances, invulnerable)) =
+        single_or_skip_mut(&mut player_query)
+    else {
+        return;
+    };
+    let player_translation = player_transform.translation.xy();
+
+    for (mut charger, mut transform, team) in &mut charger_query {
+        let position = transform.translation.xy();
+
+        match charger.aim {
+            None if charger.cooldown.just_finished() => {
+                let aim = (player_translation - position).normalize_or_zero();
+                transform.rotation = Quat::from_rotation_arc(Vec3::Y, aim.extend(0.0));
+                charger.aim = Some(aim);
+                charger.charge.reset();
+            }
+            Some(aim) if charger.charge.just_finished() => {
+                let (start, end) = charger.segment(position, aim);
+                spawn_charger_beam(&mut commands, start, end, charger.half_width);
+
+                let distance = distance_to_segment(player_translation, start, end);
+                if distance < charger.half_width
+                    && !invulnerable
+                    && player_team.is_hurt_by(*team, friendly_fire.0)
+                {
+                    let amount =
+                        Resistances::apply(resistances, DamageType::Energy, charger.damage);
+                    apply_damage(&mut commands, player, &mut health, amount);
+                }
+
+                charger.aim = None;
+                charger.cooldown.reset();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Draws a thin line along the locked aim of every charging charger, brightening as it charges.
+fn draw_charger_telegraphs(mut gizmos: Gizmos, charger_query: Query<(&Charger, &Transform)>) {
+    for (charger, transform) in &charger_query {
+        let Some(aim) = charger.aim else {
+            continue;
+        };
+
+        let (start, end) = charger.segment(transform.translation.xy(), aim);
+        let alpha = 0.2 + 0.8 * charger.charge.fraction();
+        gizmos.line_2d(start, end, BEAM_COLOR.with_alpha(alpha));
+    }
+}
+
+fn spawn_charger_beam(commands: &mut Commands, start: Vec2, end: Vec2, half_width: f32) {
+    let center = (start + end) / 2.0;
+    let direction = (end - start).normalize_or_zero();
+
+    commands.spawn((
+        Name::new("Charger Beam"),
+        SpriteBundle {
+            sprite: Sprite {
+                color: BEAM_COLOR,
+                custom_size: Some(Vec2::new(half_width * 2.0, start.distance(end))),
+                ..default()
+            },
+            transform: Transform::from_translation(center.extend(0.5))
+                .with_rotation(Quat::from_rotation_arc(Vec3::Y, direction.extend(0.0))),
+            ..default()
+        },
+        ChargerBeam,
+        Lifetime::from_seconds(0.3),
+        StateScoped(Screen::Playing),
+    ));
+}
+
+/// Fades fired beams out over their lifetime.
+fn fade_charger_beams(mut beam_query: Query<(&Lifetime, &mut Sprite), With<ChargerBeam>>) {
+    for (lifetime, mut sprite) in &mut beam_query {
+        sprite.color.set_alpha(1.0 - lifetime.fraction());
+    }
+}
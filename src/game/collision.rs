@@ -0,0 +1,187 @@
+//! Enemies bouncing off each other when they bump into one another.
+
+use bevy::{prelude::*, transform::TransformSystem, utils::HashMap};
+
+use super::{combat::Knockback, enemy::Enemy};
+use crate::{screen::Screen, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<EnemyBody>();
+    app.add_systems(
+        Update,
+        add_enemy_bodies
+            .in_set(AppSet::Update)
+            .run_if(in_state(Screen::Playing)),
+    );
+    // after every system that moves enemies, and before their transforms are propagated
+    app.add_systems(
+        PostUpdate,
+        (track_enemy_velocity, resolve_enemy_collisions)
+            .chain()
+            .before(TransformSystem::TransformPropagate)
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+/// Collision radius of an enemy at a scale of 1.
+const ENEMY_RADIUS: f32 = 14.0;
+/// Fraction of the speed at which two enemies approach each other that they bounce apart with.
+const RESTITUTION: f32 = 0.6;
+/// How long the bounce from a collision takes to wear off.
+const BOUNCE_SECONDS: f32 = 0.25;
+/// Fraction of the overlap removed each frame. Below 1 so crowds settle instead of jittering.
+const OVERLAP_CORRECTION: f32 = 0.8;
+/// Tracked speeds and bounces are capped to this, so teleports don't count as ramming speed
+/// and bounces in a dense crowd don't pile up.
+const MAX_TRACKED_SPEED: f32 = 1000.0;
+/// Side of a cell of the grid used to find nearby enemies. Two enemies touching each other
+/// are always in the same or neighbouring cells, up to a radius of half of this.
+const GRID_CELL_SIZE: f32 = 4.0 * ENEMY_RADIUS;
+
+/// A circle that pushes other enemies' bodies away. Larger, heavier enemies are pushed less.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct EnemyBody {
+    pub radius: f32,
+    pub mass: f32,
+    /// Velocity over the last frame, whatever moved the enemy.
+    velocity: Vec2,
+    previous: Vec2,
+}
+
+/// Gives every new enemy a body sized by its scale.
+fn add_enemy_bodies(
+    mut commands: Commands,
+    enemy_query: Query<(Entity, &Transform), (Added<Enemy>, Without<EnemyBody>)>,
+) {
+    for (enemy, transform) in &enemy_query {
+        let scale = transform.scale.x;
+        commands.entity(enemy).insert(EnemyBody {
+            radius: ENEMY_RADIUS * scale,
+            mass: scale * scale,
+            velocity: Vec2::ZERO,
+            previous: transform.translation.xy(),
+        });
+    }
+}
+
+fn track_enemy_velocity(time: Res<Time>, mut body_query: Query<(&mut EnemyBody, &Transform)>) {
+    // nothing moves while the game is paused
+    let delta = time.delta_seconds();
+    if delta <= 0.0 {
+        return;
+    }
+
+    for (mut body, transform) in &mut body_query {
+        let position = transform.translation.xy();
+        body.velocity = ((position - body.previous) / delta).clamp_length_max(MAX_TRACKED_SPEED);
+        body.previous = position;
+    }
+}
+
+/// Pushes overlapping enemies apart, and bounces enemies that are moving into each other
+/// apart with an impulse along the line between them. Bounces are applied as [`Knockback`].
+fn resolve_enemy_collisions(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut body_query: Query<(
+        Entity,
+        &mut EnemyBody,
+        &mut Transform,
+        Option<&mut Knockback>,
+    )>,
+) {
+    if time.delta_seconds() <= 0.0 {
+        return;
+    }
+
+    let bodies: Vec<_> = body_query
+        .iter()
+        .map(|(_, body, transform, _)| {
+            (
+                transform.translation.xy(),
+                body.radius,
+                body.mass,
+                body.velocity,
+            )
+        })
+        .collect();
+    let mut grid: HashMap<IVec2, Vec<usize>> = HashMap::new();
+    for (index, (position, ..)) in bodies.iter().enumerate() {
+        grid.entry(grid_cell(*position)).or_default().push(index);
+    }
+
+    let mut corrections = vec![Vec2::ZERO; bodies.len()];
+    let mut impulses = vec![Vec2::ZERO; bodies.len()];
+    for (i, &(position, radius, mass, velocity)) in bodies.iter().enumerate() {
+        let cell = grid_cell(position);
+        for offset in [-1, 0, 1]
+            .into_iter()
+            .flat_map(|x| [-1, 0, 1].map(|y| IVec2::new(x, y)))
+        {
+            let Some(neighbours) = grid.get(&(cell + offset)) else {
+                continue;
+            };
+            // each pair is handled once, by its lower index
+            for &j in neighbours.iter().filter(|&&j| j > i) {
+                let (other_position, other_radius, other_mass, other_velocity) = bodies[j];
+                let between = other_position - position;
+                let distance = between.length();
+                let overlap = radius + other_radius - distance;
+                if overlap <= 0.0 {
+                    continue;
+                }
+                let normal = if distance > 0.0 {
+                    between / distance
+                } else {
+                    Vec2::X
+                };
+
+                // the lighter enemy takes more of the push
+                let total_mass = mass + other_mass;
+                let correction = normal * overlap * OVERLAP_CORRECTION;
+                corrections[i] -= correction * (other_mass / total_mass);
+                corrections[j] += correction * (mass / total_mass);
+
+                let approach_speed = (other_velocity - velocity).dot(normal);
+                if approach_speed < 0.0 {
+                    let impulse =
+                        -(1.0 + RESTITUTION) * approach_speed / (1.0 / mass + 1.0 / other_mass);
+                    impulses[i] -= normal * impulse / mass;
+                    impulses[j] += normal * impulse / other_mass;
+                }
+            }
+        }
+    }
+
+    for (index, (entity, mut body, mut transform, knockback)) in body_query.iter_mut().enumerate() {
+        let correction = corrections[index];
+        if correction != Vec2::ZERO {
+            transform.translation += correction.extend(0.0);
+            // being pushed isn't moving under its own power
+            body.previous += correction;
+        }
+
+        let impulse = impulses[index];
+        if impulse == Vec2::ZERO {
+            continue;
+        }
+        match knockback {
+            Some(mut knockback) => {
+                knockback.velocity =
+                    (knockback.velocity + impulse).clamp_length_max(MAX_TRACKED_SPEED);
+                knockback.timer.reset();
+            }
+            None => {
+                commands.entity(entity).insert(Knockback::new(
+                    impulse.clamp_length_max(MAX_TRACKED_SPEED),
+                    BOUNCE_SECONDS,
+                ));
+            }
+        }
+    }
+}
+
+fn grid_cell(position: Vec2) -> IVec2 {
+    (position / GRID_CELL_SIZE).floor().as_ivec2()
+}
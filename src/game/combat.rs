@@ -0,0 +1,1139 @@
+//! Contact damage between the player, enemies and hazards, and damage over time.
+
+use bevy::{ecs::schedule::SystemConfigs, prelude::*};
+
+use super::{
+    enemy::{Enemy, HazardTile},
+    lifetime::Lifetime,
+    movement::Focused,
+    player::{Phoenix, PhoenixRevived, Repairing},
+    single_or_skip_mut,
+    spawn::player::Player,
+    stats::RunStats,
+    TimestepMode,
+};
+use crate::{
+    camera::{CameraPunch, BOUNDS},
+    screen::Screen,
+    settings::Settings,
+    AppSet,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(
+        Health,
+        MaxHealth,
+        Team,
+        FriendlyFire,
+        Resistances,
+        Ignites,
+        Burn,
+        PhasingThrough,
+        DamageCooldown,
+        Invulnerable,
+        HitFlash,
+        ContactBehavior,
+    )>();
+    app.register_type::<(Blast, Volatile, ChainReaction, Knockback)>();
+    app.observe(react_to_damage);
+    app.observe(punch_camera_on_kill);
+    app.observe(explode_volatile);
+    app.init_resource::<FriendlyFire>();
+    app.add_systems(
+        Update,
+        (
+            contact_damage().run_if(resource_equals(TimestepMode::Variable)),
+            ignite_system,
+            burn_system,
+            phase_through_system,
+            knockback_system,
+            expire_damage_cooldowns,
+            hit_flash_system,
+            draw_blasts,
+            draw_volatile_markers,
+            draw_focused_hitbox,
+            player_death_system,
+            despawn_dead_enemies,
+        )
+            .chain()
+            .in_set(AppSet::Update)
+            .run_if(in_state(Screen::Playing)),
+    );
+    app.add_systems(
+        Update,
+        (
+            tick_burns,
+            tick_phasing,
+            tick_knockbacks,
+            tick_damage_cooldowns,
+            tick_hit_flashes,
+        )
+            .in_set(AppSet::TickTimers)
+            .run_if(in_state(Screen::Playing)),
+    );
+    app.add_systems(
+        FixedUpdate,
+        contact_damage()
+            .run_if(resource_equals(TimestepMode::Fixed))
+            .in_set(AppSet::Update)
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+/// [`collision_system`], added to the schedule the [`TimestepMode`] picks.
+fn contact_damage() -> SystemConfigs {
+    // nothing can collide with the player without enemies or hazards around
+    collision_system
+        .run_if(any_with_component::<Enemy>.or_else(any_with_component::<HazardTile>))
+        .into_configs()
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Health {
+    pub value: f32,
+}
+
+/// The most [`Health`] the entity can be repaired or revived to.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct MaxHealth {
+    pub value: f32,
+}
+
+/// Which side an entity fights for. Collisions only hurt entities on opposing teams.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+pub enum Team {
+    Friendly,
+    Hostile,
+    Neutral,
+}
+
+impl Team {
+    /// Whether an entity on this team takes damage from contact with one on the `other` team.
+    pub fn is_hurt_by(self, other: Team, friendly_fire: bool) -> bool {
+        match (self, other) {
+            (Team::Neutral, _) | (_, Team::Neutral) => false,
+            (team, other) if team == other => friendly_fire,
+            _ => true,
+        }
+    }
+}
+
+/// The kind of damage an attack deals, used to look up [`Resistances`].
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageType {
+    Kinetic,
+    Energy,
+    Explosive,
+    Corrosive,
+}
+
+/// Damage multipliers per [`DamageType`]. Use 0 for immunity.
+/// Entities without this component take full damage of every type.
+#[derive(Component, Reflect, Debug, Clone, Copy)]
+#[reflect(Component)]
+pub struct Resistances {
+    pub kinetic: f32,
+    pub energy: f32,
+    pub explosive: f32,
+    pub corrosive: f32,
+}
+
+impl Default for Resistances {
+    fn default() -> Self {
+        Self {
+            kinetic: 1.0,
+            energy: 1.0,
+            explosive: 1.0,
+            corrosive: 1.0,
+        }
+    }
+}
+
+impl Resistances {
+    pub fn multiplier(&self, damage_type: DamageType) -> f32 {
+        match damage_type {
+            DamageType::Kinetic => self.kinetic,
+            DamageType::Energy => self.energy,
+            DamageType::Explosive => self.explosive,
+            DamageType::Corrosive => self.corrosive,
+        }
+    }
+
+    /// Scales `amount` of `damage_type` damage by the resistances of the target, if it has any.
+    pub fn apply(resistances: Option<&Self>, damage_type: DamageType, amount: f32) -> f32 {
+        resistances.map_or(amount, |resistances| {
+            amount * resistances.multiplier(damage_type)
+        })
+    }
+}
+
+/// When enabled, entities on the same team can damage each other.
+#[derive(Resource, Reflect, Debug, Default)]
+#[reflect(Resource)]
+pub struct FriendlyFire(pub bool);
+
+/// Sets opposing entities that come within `radius` on fire.
+#[derive(Component, Reflect, Debug, Clone, Copy)]
+#[reflect(Component)]
+pub struct Ignites {
+    pub dps: f32,
+    /// How long the [`Burn`] lasts, in seconds.
+    pub duration: f32,
+    pub radius: f32,
+}
+
+/// Deals `dps` damage per second until `timer` finishes.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Burn {
+    pub dps: f32,
+    pub timer: Timer,
+    /// Sprite colour to restore once the burn ends.
+    original_color: Color,
+}
+
+/// Sprite tint of burning entities.
+const BURN_COLOR: Color = Color::srgb(1.0, 0.45, 0.1);
+
+/// Flashes the sprite red after a hit, fading back to its own colour until `timer` finishes.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct HitFlash {
+    pub timer: Timer,
+    /// Sprite colour to fade back to.
+    original_color: Color,
+}
+
+const HIT_FLASH_COLOR: Color = Color::srgb(1.0, 0.1, 0.1);
+const HIT_FLASH_DURATION: f32 = 0.2;
+/// Hits smaller than this don't flash, so damage dealt a little every frame doesn't
+/// keep the sprite red.
+const MIN_FLASH_DAMAGE: f32 = 1.0;
+
+/// Health was taken off an entity by [`apply_damage`]. Triggered on the damaged entity.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct Damaged {
+    pub amount: f32,
+}
+
+/// Takes `amount` off `health`, never below zero, and triggers [`Damaged`] on `entity`.
+/// Every source of damage goes through here so the player and enemies react to hits alike.
+/// Whoever runs out of health is picked up by [`player_death_system`] or
+/// [`despawn_dead_enemies`] at the end of the frame. Returns the health actually lost.
+pub fn apply_damage(
+    commands: &mut Commands,
+    entity: Entity,
+    health: &mut Health,
+    amount: f32,
+) -> f32 {
+    let lost = amount.min(health.value).max(0.0);
+    if lost > 0.0 {
+        health.value -= lost;
+        commands.trigger_targets(Damaged { amount: lost }, entity);
+    }
+    lost
+}
+
+/// Distance from `point` to the closest point on the segment between `start` and `end`.
+pub fn distance_to_segment(point: Vec2, start: Vec2, end: Vec2) -> f32 {
+    let segment = end - start;
+    let length_squared = segment.length_squared();
+    if length_squared == 0.0 {
+        return point.distance(start);
+    }
+
+    // project the point onto the segment, clamped to its end points
+    let t = ((point - start).dot(segment) / length_squared).clamp(0.0, 1.0);
+    point.distance(start + segment * t)
+}
+
+/// The direction something travelling along `direction` bounces off in after hitting a surface
+/// facing `normal`, keeping its speed. The normal doesn't need to be unit length.
+pub fn reflect_projectile(direction: Vec2, normal: Vec2) -> Vec2 {
+    let normal = normal.normalize_or_zero();
+    direction - 2.0 * direction.dot(normal) * normal
+}
+
+/// Enemies closer than this to the player ram it.
+const ENEMY_CONTACT_DISTANCE: f32 = 30.0;
+/// How fast an enemy that rammed the player is pushed through and out the other side.
+const PHASE_THROUGH_SPEED: f32 = 600.0;
+
+/// An enemy that just rammed the player and is passing through it.
+/// It can't hurt the player again until it is out the other side.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct PhasingThrough {
+    pub direction: Vec2,
+    /// Gives up on pushing the enemy through if it gets stuck, e.g. against the level bounds.
+    pub timer: Timer,
+}
+
+/// Shoves the entity along `velocity`, slowing to a stop until `timer` finishes.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Knockback {
+    pub velocity: Vec2,
+    pub timer: Timer,
+}
+
+impl Knockback {
+    pub fn new(velocity: Vec2, seconds: f32) -> Self {
+        Self {
+            velocity,
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+        }
+    }
+}
+
+/// Takes no contact, hazard or beam damage while present.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Invulnerable;
+
+/// An enemy that rammed the player recently and can't hurt it again until `timer` finishes,
+/// even if it is still overlapping the player, e.g. when pinned against the level bounds.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct DamageCooldown {
+    pub timer: Timer,
+}
+
+/// Seconds before an enemy can ram the player again.
+const RAM_COOLDOWN: f32 = 1.0;
+
+/// What happens to an enemy when it rams the player. Enemies without one phase through.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Default)]
+#[reflect(Component)]
+pub enum ContactBehavior {
+    /// Pushed through the player and out the other side.
+    #[default]
+    PhaseThrough,
+    /// Knocked back the way it came.
+    Bounce,
+    /// Explodes on contact, dealing `damage` on top of the ramming damage, and is destroyed.
+    Kamikaze { damage: f32 },
+}
+
+/// The ring expanding out to `radius` where something exploded. Purely visual.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Blast {
+    pub radius: f32,
+}
+
+const BLAST_COLOR: Color = Color::srgb(1.0, 0.6, 0.2);
+const KAMIKAZE_BLAST_RADIUS: f32 = 60.0;
+
+/// Explodes when killed, dealing explosive `damage` to every other enemy within `radius`.
+/// Volatile enemies caught in the explosion that die from it explode in turn, each link of
+/// the chain a tier bigger than the last, up to [`MAX_VOLATILE_RADIUS`] and
+/// [`MAX_VOLATILE_DAMAGE`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Volatile {
+    pub radius: f32,
+    pub damage: f32,
+}
+
+impl Default for Volatile {
+    fn default() -> Self {
+        Self {
+            radius: 90.0,
+            damage: 40.0,
+        }
+    }
+}
+
+/// How much bigger each link of a chain reaction explodes than the one that set it off.
+const VOLATILE_TIER_GROWTH: f32 = 0.25;
+const MAX_VOLATILE_RADIUS: f32 = 180.0;
+const MAX_VOLATILE_DAMAGE: f32 = 80.0;
+/// An enemy killed within this many seconds of being caught in an explosion
+/// counts as part of its chain.
+const CHAIN_WINDOW: f32 = 0.5;
+
+/// Caught in the explosion of a chain reaction that is `depth` explosions long so far.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct ChainReaction {
+    pub depth: u32,
+    /// Elapsed time when the enemy was caught.
+    at_secs: f32,
+}
+
+/// Detects collisions between enemies and the player, and reduces health.
+fn collision_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    friendly_fire: Res<FriendlyFire>,
+    mut player_query: Query<
+        (
+            Entity,
+            &mut Health,
+            &Transform,
+            &Team,
+            Option<&Resistances>,
+            Has<Invulnerable>,
+        ),
+        With<Player>,
+    >,
+    enemy_query: Query<
+        (Entity, &Transform, &Team, Option<&ContactBehavior>),
+        (
+            With<Enemy>,
+            Without<PhasingThrough>,
+            Without<DamageCooldown>,
+        ),
+    >,
+    hazard_query: Query<(&HazardTile, &Transform, &Team)>,
+) {
+    let Some((player, mut health, player_transform, player_team, resistances, invulnerable)) =
+        single_or_skip_mut(&mut player_query)
+    else {
+        return;
+    };
+    if invulnerable {
+        return;
+    }
+    let player_translation = player_transform.translation;
+
+    // (enemy and its contact behavior, position, team, collision distance, damage)
+    // of everything that can hurt the player
+    let enemy_hits = enemy_query
+        .iter()
+        .map(|(enemy, enemy_transform, team, behavior)| {
+            (
+                Some((enemy, behavior.copied().unwrap_or_default())),
+                enemy_transform.translation,
+                team,
+                ENEMY_CONTACT_DISTANCE,
+                10.0,
+            )
+        });
+    // hazards have no cooldown, so they hurt by the second to not depend on the frame rate
+    let hazard_hits = hazard_query.iter().map(|(tile, tile_transform, team)| {
+        (
+            None,
+            tile_transform.translation,
+            team,
+            20.0,
+            tile.dps * time.delta_seconds(),
+        )
+    });
+
+    for (enemy, translation, team, collision_distance, damage) in enemy_hits.chain(hazard_hits) {
+        if !player_team.is_hurt_by(*team, friendly_fire.0) {
+            continue;
+        }
+
+        // compare in 2D so the depth of the hazard tiles doesn't matter
+        let distance: f32 = player_translation.xy().distance(translation.xy());
+
+        if distance < collision_distance {
+            // ramming and hazard contact are both physical hits
+            let mut amount = Resistances::apply(resistances, DamageType::Kinetic, damage);
+            if let Some((_, ContactBehavior::Kamikaze { damage })) = enemy {
+                amount += Resistances::apply(resistances, DamageType::Explosive, damage);
+            }
+            apply_damage(&mut commands, player, &mut health, amount);
+
+            let Some((enemy, behavior)) = enemy else {
+                continue;
+            };
+            commands.entity(enemy).insert(DamageCooldown {
+                timer: Timer::from_seconds(RAM_COOLDOWN, TimerMode::Once),
+            });
+            // push rammers through or away from the player instead of letting them park on top of it
+            let direction = (player_translation.xy() - translation.xy()).normalize_or(Vec2::Y);
+            match behavior {
+                ContactBehavior::PhaseThrough => {
+                    commands.entity(enemy).insert(PhasingThrough {
+                        direction,
+                        timer: Timer::from_seconds(0.5, TimerMode::Once),
+                    });
+                }
+                ContactBehavior::Bounce => {
+                    commands.entity(enemy).insert(PhasingThrough {
+                        direction: -direction,
+                        timer: Timer::from_seconds(0.5, TimerMode::Once),
+                    });
+                }
+                // the enemy destroys itself, so it doesn't count as a kill
+                ContactBehavior::Kamikaze { .. } => {
+                    commands.entity(enemy).despawn_recursive();
+                    commands.spawn((
+                        Name::new("Kamikaze Blast"),
+                        SpatialBundle::from_transform(Transform::from_translation(translation)),
+                        Blast {
+                            radius: KAMIKAZE_BLAST_RADIUS,
+                        },
+                        Lifetime::from_seconds(0.4),
+                        StateScoped(Screen::Playing),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Shows how close enemies can get to a [`Focused`] ship before they ram it.
+fn draw_focused_hitbox(mut gizmos: Gizmos, focused_query: Query<&Transform, With<Focused>>) {
+    for transform in &focused_query {
+        let position = transform.translation.xy();
+        gizmos.circle_2d(
+            position,
+            ENEMY_CONTACT_DISTANCE,
+            Color::srgba(1.0, 1.0, 1.0, 0.4),
+        );
+        gizmos.circle_2d(position, 2.0, Color::WHITE);
+    }
+}
+
+/// Draws a ring racing out from each blast over its lifetime.
+fn draw_blasts(mut gizmos: Gizmos, blast_query: Query<(&Blast, &Lifetime, &Transform)>) {
+    for (blast, lifetime, transform) in &blast_query {
+        let fraction = lifetime.fraction();
+        gizmos.circle_2d(
+            transform.translation.xy(),
+            blast.radius * fraction.sqrt(),
+            BLAST_COLOR.with_alpha(1.0 - fraction),
+        );
+    }
+}
+
+/// Flickers a small ring around every volatile enemy so the player can pick them out.
+fn draw_volatile_markers(
+    mut gizmos: Gizmos,
+    time: Res<Time>,
+    volatile_query: Query<&Transform, With<Volatile>>,
+) {
+    let alpha = 0.4 + 0.3 * (time.elapsed_seconds() * 10.0).sin();
+    for transform in &volatile_query {
+        gizmos.circle_2d(
+            transform.translation.xy(),
+            26.0,
+            BLAST_COLOR.with_alpha(alpha),
+        );
+    }
+}
+
+/// Blows up a killed volatile enemy, damaging the enemies around it. The ones it kills are
+/// despawned on a later frame, so a chain reaction plays out one link per frame.
+fn explode_volatile(
+    trigger: Trigger<EnemyKilled>,
+    mut commands: Commands,
+    time: Res<Time>,
+    mut stats: ResMut<RunStats>,
+    volatile_query: Query<(&Volatile, &Transform, Option<&ChainReaction>)>,
+    mut enemy_query: Query<(Entity, &Transform, &mut Health, Option<&Resistances>), With<Enemy>>,
+) {
+    let exploded = trigger.entity();
+    let Ok((volatile, transform, chain)) = volatile_query.get(exploded) else {
+        return;
+    };
+
+    // grow with each link of a recent chain reaction
+    let now = time.elapsed_seconds();
+    let depth = chain
+        .filter(|chain| now - chain.at_secs <= CHAIN_WINDOW)
+        .map_or(0, |chain| chain.depth);
+    let tier = 1.0 + VOLATILE_TIER_GROWTH * depth as f32;
+    let radius = (volatile.radius * tier).min(MAX_VOLATILE_RADIUS);
+    let damage = (volatile.damage * tier).min(MAX_VOLATILE_DAMAGE);
+    stats.longest_chain = stats.longest_chain.max(depth + 1);
+
+    let center = transform.translation;
+    commands.spawn((
+        Name::new("Volatile Blast"),
+        SpatialBundle::from_transform(Transform::from_translation(center)),
+        Blast { radius },
+        Lifetime::from_seconds(0.4),
+        StateScoped(Screen::Playing),
+    ));
+
+    for (enemy, enemy_transform, mut health, resistances) in &mut enemy_query {
+        if enemy == exploded || enemy_transform.translation.xy().distance(center.xy()) > radius {
+            continue;
+        }
+
+        let amount = Resistances::apply(resistances, DamageType::Explosive, damage);
+        apply_damage(&mut commands, enemy, &mut health, amount);
+        commands.entity(enemy).insert(ChainReaction {
+            depth: depth + 1,
+            at_secs: now,
+        });
+    }
+}
+
+fn tick_damage_cooldowns(time: Res<Time>, mut cooldown_query: Query<&mut DamageCooldown>) {
+    for mut cooldown in &mut cooldown_query {
+        cooldown.timer.tick(time.delta());
+    }
+}
+
+fn expire_damage_cooldowns(
+    mut commands: Commands,
+    cooldown_query: Query<(Entity, &DamageCooldown)>,
+) {
+    for (entity, cooldown) in &cooldown_query {
+        if cooldown.timer.finished() {
+            commands.entity(entity).remove::<DamageCooldown>();
+        }
+    }
+}
+
+fn tick_phasing(time: Res<Time>, mut phasing_query: Query<&mut PhasingThrough>) {
+    for mut phasing in &mut phasing_query {
+        phasing.timer.tick(time.delta());
+    }
+}
+
+/// Moves phasing enemies through the player, and lets them hit again once they are clear.
+fn phase_through_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    player_query: Query<&Transform, With<Player>>,
+    mut phasing_query: Query<(Entity, &PhasingThrough, &mut Transform), Without<Player>>,
+) {
+    let player_translation = player_query
+        .get_single()
+        .map_or(Vec2::ZERO, |transform| transform.translation.xy());
+
+    for (entity, phasing, mut transform) in &mut phasing_query {
+        let push = phasing.direction * PHASE_THROUGH_SPEED * time.delta_seconds();
+        transform.translation += push.extend(0.0);
+
+        let clear =
+            transform.translation.xy().distance(player_translation) > ENEMY_CONTACT_DISTANCE;
+        if clear || phasing.timer.finished() {
+            commands.entity(entity).remove::<PhasingThrough>();
+        }
+    }
+}
+
+/// Taking damage interrupts any repair in progress, and big enough hits flash the sprite,
+/// unless the player asked for reduced motion.
+fn react_to_damage(
+    trigger: Trigger<Damaged>,
+    mut commands: Commands,
+    settings: Res<Settings>,
+    mut target_query: Query<(Option<&Sprite>, Option<&mut HitFlash>, Has<Burn>)>,
+) {
+    let entity = trigger.entity();
+    commands.entity(entity).remove::<Repairing>();
+    if trigger.event().amount < MIN_FLASH_DAMAGE || settings.reduced_motion {
+        return;
+    }
+
+    let Ok((sprite, hit_flash, burning)) = target_query.get_mut(entity) else {
+        return;
+    };
+    // burning already tints the sprite, and the flash would restore the wrong colour
+    match (hit_flash, sprite) {
+        (Some(mut hit_flash), _) => hit_flash.timer.reset(),
+        (None, Some(sprite)) if !burning => {
+            commands.entity(entity).insert(HitFlash {
+                timer: Timer::from_seconds(HIT_FLASH_DURATION, TimerMode::Once),
+                original_color: sprite.color,
+            });
+        }
+        _ => {}
+    }
+}
+
+fn tick_knockbacks(time: Res<Time>, mut knockback_query: Query<&mut Knockback>) {
+    for mut knockback in &mut knockback_query {
+        knockback.timer.tick(time.delta());
+    }
+}
+
+/// Moves knocked back entities, bouncing them off the edges of the level.
+fn knockback_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut knockback_query: Query<(Entity, &mut Knockback, &mut Transform)>,
+) {
+    for (entity, mut knockback, mut transform) in &mut knockback_query {
+        if knockback.timer.finished() {
+            commands.entity(entity).remove::<Knockback>();
+            continue;
+        }
+
+        let speed = 1.0 - knockback.timer.fraction();
+        let destination =
+            transform.translation.xy() + knockback.velocity * speed * time.delta_seconds();
+        let clamped = destination.clamp(-BOUNDS / 2.0, BOUNDS / 2.0);
+        transform.translation = clamped.extend(transform.translation.z);
+
+        // bounce off each edge the entity was pushed past, whose normal points back into the level
+        let pushed_back = clamped - destination;
+        for normal in [pushed_back.with_y(0.0), pushed_back.with_x(0.0)] {
+            if normal != Vec2::ZERO {
+                knockback.velocity = reflect_projectile(knockback.velocity, normal);
+            }
+        }
+    }
+}
+
+fn tick_hit_flashes(time: Res<Time>, mut flash_query: Query<&mut HitFlash>) {
+    for mut hit_flash in &mut flash_query {
+        hit_flash.timer.tick(time.delta());
+    }
+}
+
+/// Fades flashing sprites from red back to their own colour, and ends the flash once it is done.
+/// Flashes wait while the entity burns, so the burn restores the colour it started from first.
+fn hit_flash_system(
+    mut commands: Commands,
+    mut flash_query: Query<(Entity, &HitFlash, &mut Sprite), Without<Burn>>,
+) {
+    for (entity, hit_flash, mut sprite) in &mut flash_query {
+        if hit_flash.timer.finished() {
+            sprite.color = hit_flash.original_color;
+            commands.entity(entity).remove::<HitFlash>();
+        } else {
+            sprite.color =
+                HIT_FLASH_COLOR.mix(&hit_flash.original_color, hit_flash.timer.fraction());
+        }
+    }
+}
+
+/// Burns entities that touch an [`Ignites`] source. Touching one again refreshes the burn.
+fn ignite_system(
+    mut commands: Commands,
+    friendly_fire: Res<FriendlyFire>,
+    source_query: Query<(&Ignites, &Transform, &Team)>,
+    mut target_query: Query<
+        (Entity, &Transform, &Team, &mut Sprite, Option<&mut Burn>),
+        (With<Health>, Without<Ignites>),
+    >,
+) {
+    for (ignites, source_transform, source_team) in &source_query {
+        let source_translation = source_transform.translation.xy();
+
+        for (entity, transform, team, mut sprite, burn) in &mut target_query {
+            if !team.is_hurt_by(*source_team, friendly_fire.0) {
+                continue;
+            }
+            if transform.translation.xy().distance(source_translation) >= ignites.radius {
+                continue;
+            }
+
+            match burn {
+                Some(mut burn) => {
+                    burn.dps = burn.dps.max(ignites.dps);
+                    burn.timer = Timer::from_seconds(ignites.duration, TimerMode::Once);
+                }
+                None => {
+                    commands.entity(entity).insert(Burn {
+                        dps: ignites.dps,
+                        timer: Timer::from_seconds(ignites.duration, TimerMode::Once),
+                        original_color: sprite.color,
+                    });
+                    sprite.color = BURN_COLOR;
+                }
+            }
+        }
+    }
+}
+
+fn tick_burns(time: Res<Time>, mut burn_query: Query<&mut Burn>) {
+    for mut burn in &mut burn_query {
+        burn.timer.tick(time.delta());
+    }
+}
+
+/// Damages burning entities, and puts them out once their burn has expired.
+fn burn_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut burn_query: Query<(Entity, &Burn, &mut Health, &mut Sprite)>,
+) {
+    for (entity, burn, mut health, mut sprite) in &mut burn_query {
+        apply_damage(
+            &mut commands,
+            entity,
+            &mut health,
+            burn.dps * time.delta_seconds(),
+        );
+
+        if burn.timer.finished() {
+            sprite.color = burn.original_color;
+            commands.entity(entity).remove::<Burn>();
+        }
+    }
+}
+
+/// Ends the run once the player's health has run out, whatever dealt the final blow.
+/// A [`Phoenix`] buff saves the player once instead.
+fn player_death_system(
+    mut commands: Commands,
+    mut next_screen: ResMut<NextState<Screen>>,
+    player_query: Query<(Entity, &Health, Has<Phoenix>), With<Player>>,
+) {
+    for (player, health, has_phoenix) in &player_query {
+        if health.value > 0.0 {
+            continue;
+        }
+
+        if has_phoenix {
+            commands.entity(player).remove::<Phoenix>();
+            commands.trigger_targets(PhoenixRevived, player);
+        } else {
+            info!("Player defeated!");
+            next_screen.set(Screen::GameOver);
+        }
+    }
+}
+
+/// An enemy's health ran out. Triggered on the enemy just before it is despawned.
+#[derive(Event, Debug)]
+pub struct EnemyKilled;
+
+/// Removes enemies whose health has run out. Other destructible entities, such as portals,
+/// handle their own destruction and don't count as kills.
+fn despawn_dead_enemies(
+    mut commands: Commands,
+    enemy_query: Query<(Entity, &Health), With<Enemy>>,
+) {
+    for (entity, health) in &enemy_query {
+        if health.value <= 0.0 {
+            commands.trigger_targets(EnemyKilled, entity);
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Punches the camera in on every kill, unless the player asked for reduced motion.
+fn punch_camera_on_kill(
+    _trigger: Trigger<EnemyKilled>,
+    settings: Res<Settings>,
+    mut camera_query: Query<&mut CameraPunch>,
+) {
+    if settings.reduced_motion {
+        return;
+    }
+    for mut punch in &mut camera_query {
+        punch.start();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{ecs::system::RunSystemOnce, time::TimeUpdateStrategy};
+
+    use super::*;
+
+    /// How many times [`EnemyKilled`] was triggered.
+    #[derive(Resource, Default)]
+    struct Kills(u32);
+
+    fn count_kills(_trigger: Trigger<EnemyKilled>, mut kills: ResMut<Kills>) {
+        kills.0 += 1;
+    }
+
+    fn death_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.init_resource::<NextState<Screen>>();
+        app.init_resource::<Kills>();
+        app.observe(count_kills);
+        app.add_systems(Update, (player_death_system, despawn_dead_enemies));
+        app
+    }
+
+    #[test]
+    fn opposing_teams_hurt_each_other_regardless_of_friendly_fire() {
+        for friendly_fire in [false, true] {
+            assert!(Team::Friendly.is_hurt_by(Team::Hostile, friendly_fire));
+            assert!(Team::Hostile.is_hurt_by(Team::Friendly, friendly_fire));
+        }
+    }
+
+    #[test]
+    fn same_team_only_hurts_with_friendly_fire() {
+        for team in [Team::Friendly, Team::Hostile] {
+            assert!(!team.is_hurt_by(team, false));
+            assert!(team.is_hurt_by(team, true));
+        }
+    }
+
+    #[test]
+    fn neutral_is_never_hurt_or_hurts() {
+        for team in [Team::Friendly, Team::Hostile, Team::Neutral] {
+            for friendly_fire in [false, true] {
+                assert!(!Team::Neutral.is_hurt_by(team, friendly_fire));
+                assert!(!team.is_hurt_by(Team::Neutral, friendly_fire));
+            }
+        }
+    }
+
+    #[test]
+    fn immune_enemy_takes_no_damage_of_that_type() {
+        let resistances = Resistances {
+            energy: 0.0,
+            ..default()
+        };
+
+        assert_eq!(
+            Resistances::apply(Some(&resistances), DamageType::Energy, 20.0),
+            0.0
+        );
+        assert_eq!(
+            Resistances::apply(Some(&resistances), DamageType::Kinetic, 20.0),
+            20.0
+        );
+    }
+
+    #[test]
+    fn partial_resistance_scales_damage() {
+        let resistances = Resistances {
+            explosive: 0.25,
+            ..default()
+        };
+
+        assert_eq!(
+            Resistances::apply(Some(&resistances), DamageType::Explosive, 20.0),
+            5.0
+        );
+        // entities without resistances take full damage
+        assert_eq!(Resistances::apply(None, DamageType::Explosive, 20.0), 20.0);
+    }
+
+    /// Whether `a` and `b` are the same, give or take rounding errors.
+    fn approx_eq(a: Vec2, b: Vec2) -> bool {
+        a.abs_diff_eq(b, 1e-5)
+    }
+
+    #[test]
+    fn head_on_projectile_comes_straight_back() {
+        assert!(approx_eq(
+            reflect_projectile(Vec2::X, Vec2::NEG_X),
+            Vec2::NEG_X
+        ));
+        assert!(approx_eq(
+            reflect_projectile(Vec2::new(0.0, -3.0), Vec2::Y),
+            Vec2::new(0.0, 3.0)
+        ));
+    }
+
+    #[test]
+    fn angled_projectile_bounces_at_the_same_angle() {
+        assert!(approx_eq(
+            reflect_projectile(Vec2::new(1.0, -1.0), Vec2::Y),
+            Vec2::new(1.0, 1.0)
+        ));
+        // against a diagonal wall the projectile turns a right angle
+        assert!(approx_eq(
+            reflect_projectile(Vec2::X, Vec2::new(-1.0, 1.0)),
+            Vec2::Y
+        ));
+    }
+
+    #[test]
+    fn grazing_projectile_barely_turns() {
+        let incoming = Vec2::new(1.0, -0.01);
+        assert!(approx_eq(
+            reflect_projectile(incoming, Vec2::Y),
+            Vec2::new(1.0, 0.01)
+        ));
+        // one running along the surface isn't deflected at all
+        assert!(approx_eq(reflect_projectile(Vec2::X, Vec2::Y), Vec2::X));
+    }
+
+    #[test]
+    fn reflection_keeps_speed_and_ignores_normal_length() {
+        for degrees in (0..360).step_by(15) {
+            let incoming = Vec2::from_angle((degrees as f32).to_radians()) * 4.0;
+            let reflected = reflect_projectile(incoming, Vec2::new(0.0, 5.0));
+
+            assert!((reflected.length() - 4.0).abs() < 1e-5);
+            assert!(approx_eq(reflected, reflect_projectile(incoming, Vec2::Y)));
+            // the angle to the surface is mirrored
+            assert!((reflected.y + incoming.y).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn burn_deals_dps_times_duration() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        let step = Duration::from_millis(100);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(step));
+        app.add_systems(Update, (tick_burns, burn_system).chain());
+        // the first update only starts the clock
+        app.update();
+
+        let enemy = app
+            .world_mut()
+            .spawn((
+                Health { value: 100.0 },
+                Sprite::default(),
+                Burn {
+                    dps: 10.0,
+                    timer: Timer::from_seconds(2.0, TimerMode::Once),
+                    original_color: Color::WHITE,
+                },
+            ))
+            .id();
+        // run past the end of the burn to check it stops
+        for _ in 0..30 {
+            app.update();
+        }
+
+        let health = app.world().get::<Health>(enemy).unwrap();
+        assert!((100.0 - health.value - 10.0 * 2.0).abs() < 1e-3);
+        assert!(!app.world().entity(enemy).contains::<Burn>());
+    }
+
+    #[test]
+    fn hazard_tiles_hurt_opposing_players_by_the_second() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        let step = Duration::from_millis(100);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(step));
+        app.init_resource::<FriendlyFire>();
+        app.add_systems(Update, collision_system);
+        // the first update only starts the clock
+        app.update();
+
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Health { value: 100.0 },
+                Transform::default(),
+                Team::Friendly,
+            ))
+            .id();
+        let tile = |team| {
+            (
+                HazardTile {
+                    dps: 60.0,
+                    timer: Timer::from_seconds(10.0, TimerMode::Once),
+                },
+                Transform::from_xyz(5.0, 0.0, -1.0),
+                team,
+            )
+        };
+        app.world_mut().spawn(tile(Team::Hostile));
+        // a tile the player dropped themselves is harmless to them
+        app.world_mut().spawn(tile(Team::Friendly));
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let health = app.world().get::<Health>(player).unwrap();
+        assert!((100.0 - health.value - 60.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn hits_only_flash_without_reduced_motion() {
+        for reduced_motion in [false, true] {
+            let mut app = App::new();
+            app.insert_resource(Settings {
+                reduced_motion,
+                ..default()
+            });
+            app.observe(react_to_damage);
+            let player = app
+                .world_mut()
+                .spawn((Health { value: 100.0 }, Sprite::default()))
+                .id();
+
+            app.world_mut().run_system_once(
+                move |mut commands: Commands, mut health_query: Query<&mut Health>| {
+                    let mut health = health_query.get_mut(player).unwrap();
+                    apply_damage(&mut commands, player, &mut health, 10.0);
+                },
+            );
+
+            let flashed = app.world().entity(player).contains::<HitFlash>();
+            assert_eq!(flashed, !reduced_motion);
+        }
+    }
+
+    #[test]
+    fn enemy_at_zero_health_is_killed_and_despawned() {
+        let mut app = death_app();
+        let dead = app.world_mut().spawn((Enemy, Health { value: 0.0 })).id();
+        let alive = app.world_mut().spawn((Enemy, Health { value: 10.0 })).id();
+
+        app.update();
+
+        assert!(app.world().get_entity(dead).is_none());
+        assert!(app.world().get_entity(alive).is_some());
+        assert_eq!(app.world().resource::<Kills>().0, 1);
+    }
+
+    #[test]
+    fn only_enemies_count_as_kills() {
+        let mut app = death_app();
+        let portal = app.world_mut().spawn(Health { value: 0.0 }).id();
+
+        app.update();
+
+        assert!(app.world().get_entity(portal).is_some());
+        assert_eq!(app.world().resource::<Kills>().0, 0);
+    }
+
+    #[test]
+    fn lethal_damage_clamps_health_and_kills() {
+        let mut app = death_app();
+        let enemy = app.world_mut().spawn((Enemy, Health { value: 10.0 })).id();
+
+        let lost = app.world_mut().run_system_once(
+            move |mut commands: Commands, mut health_query: Query<&mut Health>| {
+                let mut health = health_query.get_mut(enemy).unwrap();
+                let lost = apply_damage(&mut commands, enemy, &mut health, 25.0);
+                assert_eq!(health.value, 0.0);
+                lost
+            },
+        );
+        assert_eq!(lost, 10.0);
+
+        app.update();
+
+        assert!(app.world().get_entity(enemy).is_none());
+        assert_eq!(app.world().resource::<Kills>().0, 1);
+    }
+
+    #[test]
+    fn player_death_ends_the_run() {
+        let mut app = death_app();
+        let player = app.world_mut().spawn((Player, Health { value: 0.0 })).id();
+
+        app.update();
+
+        assert!(matches!(
+            app.world().resource::<NextState<Screen>>(),
+            NextState::Pending(Screen::GameOver)
+        ));
+        // the player is left to the game over screen, and doesn't count as a kill
+        assert!(app.world().get_entity(player).is_some());
+        assert_eq!(app.world().resource::<Kills>().0, 0);
+    }
+
+    #[test]
+    fn phoenix_saves_the_player_once() {
+        let mut app = death_app();
+        let player = app
+            .world_mut()
+            .spawn((Player, Phoenix, Health { value: 0.0 }))
+            .id();
+
+        app.update();
+
+        assert!(!app.world().entity(player).contains::<Phoenix>());
+        assert!(matches!(
+            app.world().resource::<NextState<Screen>>(),
+            NextState::Unchanged
+        ));
+    }
+}
@@ -0,0 +1,293 @@
+//! A difficulty curve over the length of a run, loaded from an asset so it can be tuned
+//! without recompiling, and adjusted on the fly to how well the player is doing.
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::{
+    combat::{Health, MaxHealth},
+    enemy::RunTime,
+    single_or_skip,
+    spawn::player::Player,
+    stats::RunStats,
+};
+use crate::{screen::Screen, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    // Load the curve, and reload it whenever the asset changes.
+    app.register_type::<(DifficultyCurve, DifficultyCurveHandle)>();
+    app.init_asset::<DifficultyCurve>();
+    app.init_asset_loader::<DifficultyCurveLoader>();
+    app.init_resource::<DifficultyCurve>();
+    app.add_systems(Startup, load_difficulty_curve);
+    app.add_systems(
+        Update,
+        apply_difficulty_curve.run_if(on_event::<AssetEvent<DifficultyCurve>>()),
+    );
+
+    // Ease off while the player struggles and push harder while they do well.
+    app.register_type::<RubberBand>();
+    app.init_resource::<RubberBand>();
+    app.add_systems(OnEnter(Screen::Playing), reset_rubber_band);
+    app.add_systems(
+        Update,
+        rubber_band_difficulty
+            .in_set(AppSet::Update)
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+/// Asset path the difficulty curve is loaded from.
+const DIFFICULTY_PATH: &str = "difficulty.ron";
+
+/// Difficulty parameters at one point of a run.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Reflect)]
+pub struct DifficultyKeyframe {
+    /// Seconds since the start of the run.
+    pub time: f32,
+    /// Seconds between enemies spawned by each portal.
+    pub spawn_interval: f32,
+    /// Multiplier on the speed of spawned enemies.
+    pub speed_multiplier: f32,
+    /// Portals stop spawning while there are this many enemies on the field.
+    pub max_enemies: f32,
+    /// Chance of a spawned enemy being one level higher than usual.
+    pub elite_chance: f32,
+}
+
+impl DifficultyKeyframe {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        let lerp = |from: f32, to: f32| from + (to - from) * t;
+        Self {
+            time: lerp(self.time, other.time),
+            spawn_interval: lerp(self.spawn_interval, other.spawn_interval),
+            speed_multiplier: lerp(self.speed_multiplier, other.speed_multiplier),
+            max_enemies: lerp(self.max_enemies, other.max_enemies),
+            elite_chance: lerp(self.elite_chance, other.elite_chance),
+        }
+    }
+}
+
+/// Keyframes sorted by time. Values in between are interpolated,
+/// and values before the first or after the last keyframe hold steady.
+///
+/// Loaded as an asset from [`DIFFICULTY_PATH`], and copied into the resource of the same type
+/// that the spawn systems read.
+#[derive(Asset, Resource, Debug, Clone, PartialEq, Deserialize, Reflect)]
+#[reflect(Resource)]
+pub struct DifficultyCurve {
+    pub keyframes: Vec<DifficultyKeyframe>,
+}
+
+impl Default for DifficultyCurve {
+    /// Used until the asset has loaded, or if it fails to: the same curve as the shipped asset.
+    fn default() -> Self {
+        let keyframe = |time, spawn_interval, speed_multiplier, max_enemies, elite_chance| {
+            DifficultyKeyframe {
+                time,
+                spawn_interval,
+                speed_multiplier,
+                max_enemies,
+                elite_chance,
+            }
+        };
+        Self {
+            keyframes: vec![
+                keyframe(0.0, 3.0, 1.0, 20.0, 0.0),
+                keyframe(120.0, 2.0, 1.2, 35.0, 0.15),
+                keyframe(300.0, 1.2, 1.4, 50.0, 0.3),
+            ],
+        }
+    }
+}
+
+impl DifficultyCurve {
+    /// The difficulty `time` seconds into a run.
+    pub fn sample(&self, time: f32) -> DifficultyKeyframe {
+        let next = self.keyframes.iter().position(|frame| frame.time >= time);
+        match next {
+            Some(0) => self.keyframes[0],
+            Some(next) => {
+                let (from, to) = (self.keyframes[next - 1], self.keyframes[next]);
+                from.lerp(to, (time - from.time) / (to.time - from.time))
+            }
+            None => *self.keyframes.last().expect("curve has no keyframes"),
+        }
+    }
+}
+
+/// Loads a [`DifficultyCurve`] from a RON file.
+#[derive(Default)]
+struct DifficultyCurveLoader;
+
+#[derive(Debug, Error)]
+enum DifficultyCurveLoaderError {
+    #[error("could not read the difficulty curve: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse the difficulty curve: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("the difficulty curve has no keyframes")]
+    NoKeyframes,
+}
+
+impl AssetLoader for DifficultyCurveLoader {
+    type Asset = DifficultyCurve;
+    type Settings = ();
+    type Error = DifficultyCurveLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<DifficultyCurve, DifficultyCurveLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let mut curve: DifficultyCurve = ron::de::from_bytes(&bytes)?;
+        if curve.keyframes.is_empty() {
+            return Err(DifficultyCurveLoaderError::NoKeyframes);
+        }
+        curve.keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Ok(curve)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+}
+
+/// Keeps the difficulty curve asset loaded.
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+struct DifficultyCurveHandle(Handle<DifficultyCurve>);
+
+fn load_difficulty_curve(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(DifficultyCurveHandle(asset_server.load(DIFFICULTY_PATH)));
+}
+
+/// Replaces the [`DifficultyCurve`] resource whenever the asset is loaded or modified.
+/// If the asset fails to load, the default curve stays in use.
+fn apply_difficulty_curve(
+    mut events: EventReader<AssetEvent<DifficultyCurve>>,
+    handle: Res<DifficultyCurveHandle>,
+    assets: Res<Assets<DifficultyCurve>>,
+    mut curve: ResMut<DifficultyCurve>,
+) {
+    for event in events.read() {
+        if !event.is_loaded_with_dependencies(&handle.0) && !event.is_modified(&handle.0) {
+            continue;
+        }
+        if let Some(loaded) = assets.get(&handle.0) {
+            *curve = loaded.clone();
+        }
+    }
+}
+
+/// Scales the enemy cap and spawn rate of the curve by `multiplier`, between
+/// [`RUBBER_BAND_MIN`] at low health and [`RUBBER_BAND_MAX`] at full health on a long combo.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct RubberBand {
+    pub multiplier: f32,
+}
+
+impl Default for RubberBand {
+    fn default() -> Self {
+        Self { multiplier: 1.0 }
+    }
+}
+
+impl RubberBand {
+    /// `keyframe` with more enemies spawned more often for a multiplier above 1, and fewer
+    /// spawned less often below 1.
+    pub fn apply(&self, keyframe: DifficultyKeyframe) -> DifficultyKeyframe {
+        DifficultyKeyframe {
+            spawn_interval: keyframe.spawn_interval / self.multiplier,
+            max_enemies: keyframe.max_enemies * self.multiplier,
+            ..keyframe
+        }
+    }
+}
+
+const RUBBER_BAND_MIN: f32 = 0.7;
+const RUBBER_BAND_MAX: f32 = 1.3;
+/// A combo this long counts as doing as well as possible.
+const RUBBER_BAND_FULL_COMBO: u32 = 10;
+/// How quickly the multiplier follows the player's performance, per second.
+const RUBBER_BAND_RESPONSE: f32 = 0.5;
+
+fn reset_rubber_band(mut rubber_band: ResMut<RubberBand>) {
+    *rubber_band = RubberBand::default();
+}
+
+/// Eases the [`RubberBand`] toward the player's current performance, mostly judged by their
+/// health and partly by their combo.
+fn rubber_band_difficulty(
+    time: Res<Time>,
+    run_time: Res<RunTime>,
+    stats: Res<RunStats>,
+    player_query: Query<(&Health, &MaxHealth), With<Player>>,
+    mut rubber_band: ResMut<RubberBand>,
+) {
+    let Some((health, max_health)) = single_or_skip(&player_query) else {
+        return;
+    };
+
+    let health_fraction = (health.value / max_health.value).clamp(0.0, 1.0);
+    let combo = stats.active_combo(run_time.elapsed_secs());
+    let combo_fraction = combo.min(RUBBER_BAND_FULL_COMBO) as f32 / RUBBER_BAND_FULL_COMBO as f32;
+    let performance = 0.7 * health_fraction + 0.3 * combo_fraction;
+    let target = RUBBER_BAND_MIN + (RUBBER_BAND_MAX - RUBBER_BAND_MIN) * performance;
+
+    let t = (RUBBER_BAND_RESPONSE * time.delta_seconds()).min(1.0);
+    rubber_band.multiplier += (target - rubber_band.multiplier) * t;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyframe(time: f32, spawn_interval: f32, max_enemies: f32) -> DifficultyKeyframe {
+        DifficultyKeyframe {
+            time,
+            spawn_interval,
+            speed_multiplier: 1.0,
+            max_enemies,
+            elite_chance: 0.0,
+        }
+    }
+
+    fn curve() -> DifficultyCurve {
+        DifficultyCurve {
+            keyframes: vec![keyframe(10.0, 3.0, 20.0), keyframe(30.0, 1.0, 40.0)],
+        }
+    }
+
+    #[test]
+    fn sample_interpolates_between_keyframes() {
+        let sampled = curve().sample(15.0);
+
+        assert_eq!(sampled.time, 15.0);
+        assert!((sampled.spawn_interval - 2.5).abs() < 1e-5);
+        assert!((sampled.max_enemies - 25.0).abs() < 1e-5);
+        assert_eq!(curve().sample(30.0), keyframe(30.0, 1.0, 40.0));
+    }
+
+    #[test]
+    fn sample_holds_steady_outside_the_keyframes() {
+        assert_eq!(curve().sample(0.0), keyframe(10.0, 3.0, 20.0));
+        assert_eq!(curve().sample(1000.0), keyframe(30.0, 1.0, 40.0));
+    }
+
+    #[test]
+    fn shipped_curve_matches_the_default() {
+        let curve: DifficultyCurve =
+            ron::de::from_str(include_str!("../../assets/difficulty.ron")).unwrap();
+
+        assert_eq!(curve, DifficultyCurve::default());
+    }
+}
@@ -0,0 +1,1377 @@
+//! Spawn enemies and steer them toward the player.
+
+use bevy::{core::FrameCount, ecs::schedule::SystemConfigs, prelude::*, time::Stopwatch};
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    combat::{ContactBehavior, Health, Ignites, Team, Volatile},
+    single_or_skip,
+    spawn::player::Player,
+    TimestepMode,
+};
+use crate::{camera::BOUNDS, screen::Screen, settings::effects_enabled, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    // Spawn an opening set of enemies. Further enemies come from portals.
+    app.register_type::<InitialSpawn>();
+    app.init_resource::<InitialSpawn>();
+    app.add_systems(OnEnter(Screen::Playing), spawn_initial_enemies);
+
+    // Spawn tougher enemies the longer the run lasts.
+    app.register_type::<(RunTime, EnemyHealthScaling)>();
+    app.init_resource::<RunTime>();
+    app.init_resource::<EnemyHealthScaling>();
+    app.add_systems(OnEnter(Screen::Playing), reset_run_time);
+    app.add_systems(
+        Update,
+        tick_run_time
+            .in_set(AppSet::TickTimers)
+            .run_if(in_state(Screen::Playing)),
+    );
+
+    // Move enemies forward and turn them to face the player.
+    app.register_type::<(
+        Enemy,
+        EnemyKind,
+        EnemyMove,
+        SnapToPlayer,
+        RotateToPlayer,
+        AggroRadius,
+        AiTick,
+        AiUpdateRate,
+        FacePlayerOnSpawn,
+    )>();
+    app.init_resource::<AiUpdateRate>();
+    app.init_resource::<FacePlayerOnSpawn>();
+    app.add_systems(
+        Update,
+        steering_systems().run_if(resource_equals(TimestepMode::Variable)),
+    );
+    app.add_systems(
+        FixedUpdate,
+        steering_systems().run_if(resource_equals(TimestepMode::Fixed)),
+    );
+
+    // Telegraph and lunge at the player once in range.
+    app.register_type::<Lunge>();
+    app.add_systems(
+        Update,
+        (
+            tick_lunges.in_set(AppSet::TickTimers),
+            lunge_system
+                .before(enemy_movement_system)
+                .in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+
+    // Freeze stunned enemies in place until the stun wears off.
+    app.register_type::<Stunned>();
+    app.add_systems(
+        Update,
+        (
+            tick_stuns.in_set(AppSet::TickTimers),
+            (expire_stuns, draw_stun_indicators.run_if(effects_enabled)).in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+
+    // Make the survivors flee for a while when only a few enemies are left.
+    app.register_type::<(EnemyMorale, Retreating)>();
+    app.init_resource::<EnemyMorale>();
+    app.add_systems(OnEnter(Screen::Playing), reset_morale);
+    app.add_systems(
+        Update,
+        (
+            tick_retreats.in_set(AppSet::TickTimers),
+            (morale_system, expire_retreats).in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+
+    // Leave damaging tiles behind trail enemies.
+    app.register_type::<(TrailHazard, HazardTile)>();
+    app.add_systems(
+        Update,
+        (
+            (tick_trail_hazards, tick_hazard_tiles).in_set(AppSet::TickTimers),
+            (spawn_hazard_tile, despawn_expired_hazard_tiles).in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+const KAMIKAZE_COLOR: Color = Color::srgb(1.0, 0.4, 0.3);
+
+/// Chance of an enemy spawned by a portal being [`Volatile`].
+const VOLATILE_CHANCE: f32 = 0.15;
+
+/// Starting health of every enemy, before any scaling.
+pub const ENEMY_HEALTH: f32 = 30.0;
+
+/// Enemies spawned by portals only turn toward the player within this distance.
+const PORTAL_ENEMY_AGGRO_RADIUS: f32 = 450.0;
+
+/// The kinds of enemy that can be part of the opening set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum InitialEnemy {
+    /// Snaps to face the player immediately.
+    Snap,
+    /// Eases toward facing the player.
+    SmoothSnap,
+    /// Turns toward the player at a limited speed.
+    Rotate,
+    /// Turns toward the player and leaves hazard tiles behind.
+    Trail,
+    /// Eases toward facing the player and explodes on contact.
+    Kamikaze,
+}
+
+impl InitialEnemy {
+    pub fn kind(self) -> EnemyKind {
+        match self {
+            Self::Snap => EnemyKind::Snapper,
+            Self::SmoothSnap => EnemyKind::Stalker,
+            Self::Rotate => EnemyKind::Turner,
+            Self::Trail => EnemyKind::Scorcher,
+            Self::Kamikaze => EnemyKind::Kamikaze,
+        }
+    }
+
+    /// How much of the [`InitialSpawn`] threat budget one enemy of this kind uses up.
+    pub fn cost(self) -> u32 {
+        match self {
+            Self::Snap | Self::SmoothSnap | Self::Rotate => 1,
+            Self::Trail | Self::Kamikaze => 2,
+        }
+    }
+}
+
+/// The threat budget of the enemies on the field when a run starts, and how likely each kind
+/// is. Enemies are picked until no kind fits in what is left of the budget, so a few expensive
+/// enemies or a swarm of cheap ones come out about as dangerous.
+/// A `budget` of zero starts straight into the portal waves.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct InitialSpawn {
+    pub budget: u32,
+    /// Relative chance of each kind being picked for every spawned enemy.
+    pub weights: Vec<(InitialEnemy, f32)>,
+}
+
+impl InitialSpawn {
+    /// Picks enemies from the weighted table, among the kinds that still fit in the budget.
+    /// Their total cost never exceeds the budget.
+    fn pick(&self, rng: &mut impl Rng) -> Vec<InitialEnemy> {
+        let mut remaining = self.budget;
+        let mut picked = Vec::new();
+        loop {
+            let affordable: Vec<_> = self
+                .weights
+                .iter()
+                .filter(|(kind, _)| kind.cost() <= remaining)
+                .collect();
+            let Ok((kind, _)) = affordable.choose_weighted(rng, |(_, w)| *w) else {
+                return picked;
+            };
+            remaining -= kind.cost();
+            picked.push(*kind);
+        }
+    }
+}
+
+impl Default for InitialSpawn {
+    fn default() -> Self {
+        Self {
+            budget: 7,
+            weights: vec![
+                (InitialEnemy::Snap, 1.0),
+                (InitialEnemy::SmoothSnap, 1.0),
+                (InitialEnemy::Rotate, 2.0),
+                (InitialEnemy::Trail, 1.0),
+                (InitialEnemy::Kamikaze, 1.0),
+            ],
+        }
+    }
+}
+
+/// How long the current run has lasted.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct RunTime(Stopwatch);
+
+/// Seconds of survival before spawned enemies go up a level.
+const SECONDS_PER_ENEMY_LEVEL: f32 = 30.0;
+/// Highest level an enemy can spawn at.
+pub const MAX_ENEMY_LEVEL: u32 = 2;
+
+impl RunTime {
+    pub fn elapsed_secs(&self) -> f32 {
+        self.0.elapsed_secs()
+    }
+
+    /// The level of enemies spawned at this point of the run.
+    pub fn enemy_level(&self) -> u32 {
+        ((self.elapsed_secs() / SECONDS_PER_ENEMY_LEVEL) as u32).min(MAX_ENEMY_LEVEL)
+    }
+}
+
+/// Sprite tint for an enemy of the given level: white, then orange, then red.
+fn tint_for_level(level: u32) -> Color {
+    match level {
+        0 => Color::WHITE,
+        1 => Color::srgb(1.0, 0.6, 0.2),
+        _ => Color::srgb(1.0, 0.25, 0.25),
+    }
+}
+
+/// Stat multiplier for an enemy of the given level, applied to both health and speed.
+fn stat_scale_for_level(level: u32) -> f32 {
+    1.0 + 0.25 * level as f32
+}
+
+/// Extra health given to enemies spawned in later portal waves, on top of their level.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct EnemyHealthScaling {
+    /// Health added per wave after the first, as a fraction of the base health.
+    pub per_wave: f32,
+    /// The multiplier never goes above this, so late enemies don't become bullet sponges.
+    pub max_multiplier: f32,
+}
+
+impl Default for EnemyHealthScaling {
+    fn default() -> Self {
+        Self {
+            per_wave: 0.15,
+            max_multiplier: 2.5,
+        }
+    }
+}
+
+impl EnemyHealthScaling {
+    /// Health multiplier for enemies spawned during `wave`.
+    pub fn multiplier(&self, wave: u32) -> f32 {
+        (1.0 + self.per_wave * wave.saturating_sub(1) as f32).min(self.max_multiplier)
+    }
+}
+
+/// Contact damage is dealt by entities with this marker.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Enemy;
+
+/// Which kind of enemy this is, for the run stats and the bestiary.
+#[derive(
+    Component,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    Reflect,
+)]
+#[reflect(Component)]
+pub enum EnemyKind {
+    Snapper,
+    Stalker,
+    Turner,
+    Scorcher,
+    Kamikaze,
+    /// The basic enemy spawned by portals.
+    Drone,
+    Blinker,
+    Charger,
+    Juggernaut,
+    Protector,
+    Sentinel,
+}
+
+impl EnemyKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Snapper => "Snapper",
+            Self::Stalker => "Stalker",
+            Self::Turner => "Turner",
+            Self::Scorcher => "Scorcher",
+            Self::Kamikaze => "Kamikaze",
+            Self::Drone => "Drone",
+            Self::Blinker => "Blinker",
+            Self::Charger => "Charger",
+            Self::Juggernaut => "Juggernaut",
+            Self::Protector => "Protector",
+            Self::Sentinel => "Sentinel",
+        }
+    }
+
+    /// Starting health of an enemy of this kind, before any level or wave scaling.
+    pub fn base_health(self) -> f32 {
+        match self {
+            Self::Snapper
+            | Self::Stalker
+            | Self::Turner
+            | Self::Scorcher
+            | Self::Kamikaze
+            | Self::Drone => ENEMY_HEALTH,
+            Self::Blinker => 25.0,
+            Self::Charger => 50.0,
+            Self::Juggernaut => 150.0,
+            Self::Protector => 60.0,
+            Self::Sentinel => 40.0,
+        }
+    }
+}
+
+/// Moves the enemy forward along its facing.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct EnemyMove {
+    pub movement_speed: f32,
+}
+
+/// Snaps the enemy to face the player.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct SnapToPlayer {
+    /// Snap to the target facing immediately, otherwise interpolate toward it over a few frames.
+    pub instant: bool,
+}
+
+impl Default for SnapToPlayer {
+    fn default() -> Self {
+        Self { instant: true }
+    }
+}
+
+/// What a [`Lunge`] enemy is currently doing.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+enum LungeState {
+    /// Moving toward the player as usual until it is within range.
+    Approach,
+    /// Standing still and flashing while it keeps aiming at the player.
+    WindUp { original_color: Color },
+    /// Dashing forward along the heading it had when the wind-up ended.
+    Lunging,
+    /// Standing still to catch its breath before approaching again.
+    Recover,
+}
+
+/// Makes a [`SnapToPlayer`] enemy telegraph its attacks: once within `range` of the player it
+/// stops and flashes for the length of `windup`, then lunges wherever it was aiming at
+/// `speed` for the length of `lunge`, and rests for the length of `recover`.
+/// The player can dodge by moving during the wind-up.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Lunge {
+    pub range: f32,
+    pub speed: f32,
+    pub windup: Timer,
+    pub lunge: Timer,
+    pub recover: Timer,
+    state: LungeState,
+}
+
+impl Default for Lunge {
+    fn default() -> Self {
+        Self {
+            range: 180.0,
+            speed: 600.0,
+            windup: Timer::from_seconds(0.5, TimerMode::Once),
+            lunge: Timer::from_seconds(0.35, TimerMode::Once),
+            recover: Timer::from_seconds(0.6, TimerMode::Once),
+            state: LungeState::Approach,
+        }
+    }
+}
+
+impl Lunge {
+    /// The enemy's movement speed right now, given its usual `speed`.
+    fn movement_speed(&self, speed: f32) -> f32 {
+        match self.state {
+            LungeState::Approach => speed,
+            LungeState::WindUp { .. } | LungeState::Recover => 0.0,
+            LungeState::Lunging => self.speed,
+        }
+    }
+
+    /// Whether the enemy is holding its heading rather than turning toward the player.
+    fn locked(&self) -> bool {
+        matches!(self.state, LungeState::Lunging | LungeState::Recover)
+    }
+}
+
+const LUNGE_WARNING_COLOR: Color = Color::srgb(1.0, 0.3, 0.3);
+
+/// How quickly a non-instant [`SnapToPlayer`] closes the gap to its target facing, per second.
+const SNAP_SMOOTHING: f32 = 20.0;
+
+/// Rotates the enemy to face the player at a limited speed.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct RotateToPlayer {
+    /// Rotation speed in radians per second.
+    pub rotation_speed: f32,
+}
+
+/// Whether new [`RotateToPlayer`] enemies start out facing the player, instead of facing up
+/// and turning toward the player at their limited speed.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct FacePlayerOnSpawn(pub bool);
+
+impl Default for FacePlayerOnSpawn {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// A [`RotateToPlayer`] enemy only turns toward the player within this distance, and otherwise
+/// holds its heading. Enemies without one always track the player.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct AggroRadius(pub f32);
+
+impl Default for AggroRadius {
+    fn default() -> Self {
+        Self(f32::INFINITY)
+    }
+}
+
+/// Every how many frames a staggered enemy recomputes the direction it steers toward.
+/// Steering still turns the enemy smoothly every frame toward the last direction it computed.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct AiUpdateRate {
+    pub frames: u32,
+}
+
+impl Default for AiUpdateRate {
+    fn default() -> Self {
+        Self { frames: 3 }
+    }
+}
+
+/// Spreads the steering work of many enemies across frames: the enemy only recomputes its
+/// target direction on frames where `(frame + offset) % AiUpdateRate::frames == 0`.
+/// Enemies without one recompute it every frame.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct AiTick {
+    pub offset: u32,
+    /// The last direction computed, reused on the frames in between.
+    target: Option<Vec2>,
+}
+
+impl AiTick {
+    pub fn new(offset: u32) -> Self {
+        Self {
+            offset,
+            target: None,
+        }
+    }
+
+    /// The direction to steer toward this frame, recomputed with `compute` when it is due.
+    fn target(&mut self, frame: u32, rate: AiUpdateRate, compute: impl FnOnce() -> Vec2) -> Vec2 {
+        let due = frame
+            .wrapping_add(self.offset)
+            .is_multiple_of(rate.frames.max(1));
+        match self.target {
+            Some(target) if !due => target,
+            _ => *self.target.insert(compute()),
+        }
+    }
+}
+
+/// The direction an enemy steers toward this frame, using its [`AiTick`] if it has one.
+fn steering_target(
+    ai_tick: Option<Mut<AiTick>>,
+    frame: &FrameCount,
+    rate: AiUpdateRate,
+    compute: impl FnOnce() -> Vec2,
+) -> Vec2 {
+    match ai_tick {
+        Some(mut ai_tick) => ai_tick.target(frame.0, rate, compute),
+        None => compute(),
+    }
+}
+
+/// Stops the enemy from moving or turning until `timer` finishes.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Stunned {
+    pub timer: Timer,
+}
+
+/// When the enemy count drops below `retreat_below`, every enemy that steers toward the player
+/// [`Retreating`] for `retreat_duration` seconds. They only break again after the count has
+/// recovered to the threshold.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct EnemyMorale {
+    pub retreat_below: usize,
+    pub retreat_duration: f32,
+    broken: bool,
+}
+
+impl Default for EnemyMorale {
+    fn default() -> Self {
+        Self {
+            retreat_below: 4,
+            retreat_duration: 3.0,
+            broken: false,
+        }
+    }
+}
+
+/// Steers the enemy away from the player instead of toward it until `timer` finishes.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Retreating {
+    pub timer: Timer,
+}
+
+/// Periodically leaves a [`HazardTile`] behind the enemy.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct TrailHazard {
+    pub spawn_timer: Timer,
+}
+
+/// A damaging tile left behind by a [`TrailHazard`] enemy. Expires when its timer finishes.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct HazardTile {
+    /// Damage per second while the player stands on the tile.
+    pub dps: f32,
+    pub timer: Timer,
+}
+
+/// Spawns the opening enemies described by [`InitialSpawn`] around the player.
+///
+/// The Bevy coordinate system is the same for 2D and 3D, in terms of 2D this means that:
+///
+/// * `X` axis goes from left to right (`+X` points right)
+/// * `Y` axis goes from bottom to top (`+Y` point up)
+/// * `Z` axis goes from far to near (`+Z` points towards you, out of the screen)
+///
+/// The origin is at the center of the screen.
+fn spawn_initial_enemies(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    initial_spawn: Res<InitialSpawn>,
+) {
+    let enemy_a_handle: Handle<Image> = asset_server.load("enemy_A.png");
+    let enemy_b_handle: Handle<Image> = asset_server.load("enemy_B.png");
+
+    // spread the enemies evenly around an ellipse, starting on the left
+    let radii = BOUNDS / 4.0;
+    let mut rng = thread_rng();
+
+    let kinds = initial_spawn.pick(&mut rng);
+
+    for (i, kind) in kinds.iter().enumerate() {
+        let angle = std::f32::consts::PI + std::f32::consts::TAU * i as f32 / kinds.len() as f32;
+        let position = (Vec2::from_angle(angle) * radii).extend(0.0);
+
+        let mut enemy = commands.spawn((
+            Name::new(kind.kind().name()),
+            SpriteBundle {
+                transform: Transform::from_translation(position),
+                ..default()
+            },
+            Enemy,
+            kind.kind(),
+            Team::Hostile,
+            Health {
+                value: kind.kind().base_health(),
+            },
+            AiTick::new(i as u32),
+            StateScoped(Screen::Playing),
+        ));
+
+        match kind {
+            InitialEnemy::Snap => {
+                enemy.insert((
+                    enemy_a_handle.clone(),
+                    SnapToPlayer::default(),
+                    EnemyMove {
+                        movement_speed: 100.0,
+                    },
+                    Lunge::default(),
+                ));
+            }
+            InitialEnemy::SmoothSnap => {
+                enemy.insert((
+                    enemy_a_handle.clone(),
+                    SnapToPlayer { instant: false },
+                    EnemyMove {
+                        movement_speed: 160.0,
+                    },
+                    Lunge::default(),
+                ));
+            }
+            InitialEnemy::Rotate => {
+                enemy.insert((
+                    enemy_b_handle.clone(),
+                    RotateToPlayer {
+                        rotation_speed: f32::to_radians(45.0), // degrees per second
+                    },
+                    EnemyMove {
+                        movement_speed: 100.0,
+                    },
+                ));
+            }
+            InitialEnemy::Trail => {
+                enemy.insert((
+                    enemy_b_handle.clone(),
+                    RotateToPlayer {
+                        rotation_speed: f32::to_radians(60.0), // degrees per second
+                    },
+                    EnemyMove {
+                        movement_speed: 120.0,
+                    },
+                    TrailHazard {
+                        spawn_timer: Timer::from_seconds(0.5, TimerMode::Repeating),
+                    },
+                ));
+            }
+            InitialEnemy::Kamikaze => {
+                enemy.insert((
+                    enemy_a_handle.clone(),
+                    Sprite {
+                        color: KAMIKAZE_COLOR,
+                        ..default()
+                    },
+                    SnapToPlayer { instant: false },
+                    EnemyMove {
+                        movement_speed: 200.0,
+                    },
+                    ContactBehavior::Kamikaze { damage: 25.0 },
+                ));
+            }
+        }
+    }
+}
+
+fn reset_run_time(mut run_time: ResMut<RunTime>) {
+    run_time.0.reset();
+}
+
+fn tick_run_time(time: Res<Time>, mut run_time: ResMut<RunTime>) {
+    run_time.0.tick(time.delta());
+}
+
+/// Spawns a basic enemy of the given level at `position`, facing the player over time once
+/// it comes within [`PORTAL_ENEMY_AGGRO_RADIUS`].
+/// Its health and speed are further multiplied by `health_multiplier` and `speed_multiplier`,
+/// see [`EnemyHealthScaling`] and the difficulty curve.
+pub fn spawn_enemy(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    position: Vec3,
+    level: u32,
+    health_multiplier: f32,
+    speed_multiplier: f32,
+) {
+    let stat_scale = stat_scale_for_level(level);
+
+    let mut enemy = commands.spawn((
+        Name::new(EnemyKind::Drone.name()),
+        SpriteBundle {
+            sprite: Sprite {
+                color: tint_for_level(level),
+                ..default()
+            },
+            texture: asset_server.load("enemy_A.png"),
+            transform: Transform::from_translation(position),
+            ..default()
+        },
+        Enemy,
+        EnemyKind::Drone,
+        Team::Hostile,
+        Health {
+            value: EnemyKind::Drone.base_health() * stat_scale * health_multiplier,
+        },
+        EnemyMove {
+            movement_speed: (random::<f32>() * 250. + 50.) * stat_scale * speed_multiplier,
+        },
+        RotateToPlayer {
+            rotation_speed: f32::to_radians(random::<f32>() * 300. + 60.), // degrees per second
+        },
+        AggroRadius(PORTAL_ENEMY_AGGRO_RADIUS),
+        AiTick::new(random()),
+        StateScoped(Screen::Playing),
+    ));
+    if random::<f32>() < VOLATILE_CHANCE {
+        enemy.insert(Volatile::default());
+    }
+}
+
+/// The enemy movement and steering systems, added to the schedule the [`TimestepMode`] picks.
+fn steering_systems() -> SystemConfigs {
+    (
+        face_player_on_spawn.run_if(resource_equals(FacePlayerOnSpawn(true))),
+        enemy_movement_system.run_if(any_with_component::<EnemyMove>),
+        snap_to_player_system.run_if(any_with_component::<SnapToPlayer>),
+        rotate_to_player_system.run_if(any_with_component::<RotateToPlayer>),
+    )
+        .chain()
+        .in_set(AppSet::Update)
+        .run_if(in_state(Screen::Playing))
+}
+
+fn enemy_movement_system(
+    time: Res<Time>,
+    mut query: Query<(&EnemyMove, &mut Transform, Option<&Lunge>), Without<Stunned>>,
+) {
+    for (enemy, mut transform, lunge) in &mut query {
+        let movement_speed = lunge.map_or(enemy.movement_speed, |lunge| {
+            lunge.movement_speed(enemy.movement_speed)
+        });
+        // get the ship's forward vector by applying the current rotation to the ship's initial facing vector
+        let movement_direction = transform.rotation * Vec3::Y;
+        // get the distance the ship will move based on direction, the ship's movement speed, and delta time
+        let movement_distance = movement_speed * time.delta_seconds();
+        // create the change in translation using the new movement direction and distance
+        let translation_delta = movement_direction * movement_distance;
+        // update the ship translation with our new translation delta
+        transform.translation += translation_delta;
+
+        // bound the ship within the invisible level bounds
+        let extents = Vec3::from((BOUNDS / 2.0, 0.0));
+        transform.translation = transform.translation.min(extents).max(-extents);
+    }
+}
+
+/// Turns newly spawned [`RotateToPlayer`] enemies to face the player once, like
+/// [`SnapToPlayer`] does every frame, before their limited tracking takes over.
+/// Keyed on the enemy spawning rather than on [`RotateToPlayer`] being added, so enemies that
+/// only start tracking later, like alerted sentinels, still turn at their limited speed.
+fn face_player_on_spawn(
+    mut query: Query<&mut Transform, (Added<Enemy>, With<RotateToPlayer>, Without<Player>)>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    if query.is_empty() {
+        return;
+    }
+    let Some(player_transform) = single_or_skip(&player_query) else {
+        return;
+    };
+    let player_translation = player_transform.translation.xy();
+
+    for mut enemy_transform in &mut query {
+        let to_player =
+            (player_translation - enemy_transform.translation.xy()).normalize_or(Vec2::Y);
+        enemy_transform.rotation = Quat::from_rotation_arc(Vec3::Y, to_player.extend(0.));
+    }
+}
+
+/// Demonstrates snapping the enemy ship to face the player ship immediately, or easing toward it
+/// with `Quat::slerp` when the snap is not instant.
+fn snap_to_player_system(
+    time: Res<Time>,
+    frame: Res<FrameCount>,
+    rate: Res<AiUpdateRate>,
+    mut query: Query<
+        (
+            &SnapToPlayer,
+            &mut Transform,
+            Has<Retreating>,
+            Option<&mut AiTick>,
+            Option<&Lunge>,
+        ),
+        (Without<Player>, Without<Stunned>),
+    >,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let Some(player_transform) = single_or_skip(&player_query) else {
+        return;
+    };
+    // get the player translation in 2D
+    let player_translation = player_transform.translation.xy();
+
+    for (config, mut enemy_transform, retreating, ai_tick, lunge) in &mut query {
+        // a lunge holds the heading it was aimed at
+        if lunge.is_some_and(Lunge::locked) {
+            continue;
+        }
+
+        let enemy_translation = enemy_transform.translation.xy();
+        let to_player = steering_target(ai_tick, &frame, *rate, || {
+            // get the vector from the enemy ship to the player ship in 2D and normalize it.
+            let to_player = (player_translation - enemy_translation).normalize();
+            // a retreating enemy faces directly away from the player instead
+            if retreating {
+                -to_player
+            } else {
+                to_player
+            }
+        });
+
+        // get the quaternion to rotate from the initial enemy facing direction to the direction
+        // facing the player
+        let rotate_to_player = Quat::from_rotation_arc(Vec3::Y, to_player.extend(0.));
+
+        // rotate the enemy to face the player, either at once or by a fraction of the remaining
+        // angle each tick
+        enemy_transform.rotation = if config.instant {
+            rotate_to_player
+        } else {
+            let t = (SNAP_SMOOTHING * time.delta_seconds()).min(1.0);
+            enemy_transform.rotation.slerp(rotate_to_player, t)
+        };
+    }
+}
+
+/// Demonstrates rotating an enemy ship to face the player ship at a given rotation speed.
+///
+/// This method uses the vector dot product to determine if the enemy is facing the player and
+/// if not, which way to rotate to face the player. The dot product on two unit length vectors
+/// will return a value between -1.0 and +1.0 which tells us the following about the two vectors:
+///
+/// * If the result is 1.0 the vectors are pointing in the same direction, the angle between them is
+///   0 degrees.
+/// * If the result is 0.0 the vectors are perpendicular, the angle between them is 90 degrees.
+/// * If the result is -1.0 the vectors are parallel but pointing in opposite directions, the angle
+///   between them is 180 degrees.
+/// * If the result is positive the vectors are pointing in roughly the same direction, the angle
+///   between them is greater than 0 and less than 90 degrees.
+/// * If the result is negative the vectors are pointing in roughly opposite directions, the angle
+///   between them is greater than 90 and less than 180 degrees.
+///
+/// It is possible to get the angle by taking the arc cosine (`acos`) of the dot product. It is
+/// often unnecessary to do this though. Beware than `acos` will return `NaN` if the input is less
+/// than -1.0 or greater than 1.0. This can happen even when working with unit vectors due to
+/// floating point precision loss, so it pays to clamp your dot product value before calling
+/// `acos`.
+fn rotate_to_player_system(
+    time: Res<Time>,
+    frame: Res<FrameCount>,
+    rate: Res<AiUpdateRate>,
+    mut query: Query<
+        (
+            &RotateToPlayer,
+            &mut Transform,
+            Option<&AggroRadius>,
+            Has<Retreating>,
+            Option<&mut AiTick>,
+        ),
+        (Without<Player>, Without<Stunned>),
+    >,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let Some(player_transform) = single_or_skip(&player_query) else {
+        return;
+    };
+    // get the player translation in 2D
+    let player_translation = player_transform.translation.xy();
+
+    for (config, mut enemy_transform, aggro_radius, retreating, ai_tick) in &mut query {
+        // hold the current heading until the player comes within range, unless fleeing
+        let aggro_radius = aggro_radius.copied().unwrap_or_default().0;
+        if !retreating
+            && enemy_transform
+                .translation
+                .xy()
+                .distance(player_translation)
+                > aggro_radius
+        {
+            continue;
+        }
+
+        // get the enemy ship forward vector in 2D (already unit length)
+        let enemy_forward = (enemy_transform.rotation * Vec3::Y).xy();
+
+        let enemy_translation = enemy_transform.translation.xy();
+        let to_player = steering_target(ai_tick, &frame, *rate, || {
+            // get the vector from the enemy ship to the player ship in 2D and normalize it.
+            let to_player = (player_translation - enemy_translation).normalize();
+            // a retreating enemy turns to face directly away from the player instead
+            if retreating {
+                -to_player
+            } else {
+                to_player
+            }
+        });
+
+        // get the dot product between the enemy forward vector and the direction to the player.
+        let forward_dot_player = enemy_forward.dot(to_player);
+
+        // if the dot product is approximately 1.0 then the enemy is already facing the player and
+        // we can early out.
+        if (forward_dot_player - 1.0).abs() < f32::EPSILON {
+            continue;
+        }
+
+        // get the right vector of the enemy ship in 2D (already unit length)
+        let enemy_right = (enemy_transform.rotation * Vec3::X).xy();
+
+        // get the dot product of the enemy right vector and the direction to the player ship.
+        // if the dot product is negative them we need to rotate counter clockwise, if it is
+        // positive we need to rotate clockwise. Note that `copysign` will still return 1.0 if the
+        // dot product is 0.0 (because the player is directly behind the enemy, so perpendicular
+        // with the right vector).
+        let right_dot_player = enemy_right.dot(to_player);
+
+        // determine the sign of rotation from the right dot player. We need to negate the sign
+        // here as the 2D bevy co-ordinate system rotates around +Z, which is pointing out of the
+        // screen. Due to the right hand rule, positive rotation around +Z is counter clockwise and
+        // negative is clockwise.
+        let rotation_sign = -f32::copysign(1.0, right_dot_player);
+
+        // limit rotation so we don't overshoot the target. We need to convert our dot product to
+        // an angle here so we can get an angle of rotation to clamp against.
+        let max_angle = forward_dot_player.clamp(-1.0, 1.0).acos(); // clamp acos for safety
+
+        // calculate angle of rotation with limit
+        let rotation_angle =
+            rotation_sign * (config.rotation_speed * time.delta_seconds()).min(max_angle);
+
+        // rotate the enemy to face the player
+        enemy_transform.rotate_z(rotation_angle);
+    }
+}
+
+fn tick_lunges(time: Res<Time>, mut lunge_query: Query<&mut Lunge>) {
+    for mut lunge in &mut lunge_query {
+        let timer = match lunge.state {
+            LungeState::Approach => continue,
+            LungeState::WindUp { .. } => &mut lunge.windup,
+            LungeState::Lunging => &mut lunge.lunge,
+            LungeState::Recover => &mut lunge.recover,
+        };
+        timer.tick(time.delta());
+    }
+}
+
+/// Moves lunging enemies on to their next state, and flashes the ones winding up.
+fn lunge_system(
+    player_query: Query<&Transform, With<Player>>,
+    mut lunge_query: Query<(&mut Lunge, &Transform, &mut Sprite), Without<Player>>,
+) {
+    let Some(player_transform) = single_or_skip(&player_query) else {
+        return;
+    };
+    let player_translation = player_transform.translation.xy();
+
+    for (mut lunge, transform, mut sprite) in &mut lunge_query {
+        match lunge.state {
+            LungeState::Approach => {
+                if transform.translation.xy().distance(player_translation) < lunge.range {
+                    lunge.state = LungeState::WindUp {
+                        original_color: sprite.color,
+                    };
+                    lunge.windup.reset();
+                }
+            }
+            LungeState::WindUp { original_color } if lunge.windup.just_finished() => {
+                sprite.color = original_color;
+                lunge.state = LungeState::Lunging;
+                lunge.lunge.reset();
+            }
+            LungeState::WindUp { original_color } => {
+                // pulse between the base colour and the warning colour
+                let pulse = (lunge.windup.elapsed_secs() * 20.0).sin() * 0.5 + 0.5;
+                sprite.color = original_color.mix(&LUNGE_WARNING_COLOR, pulse);
+            }
+            LungeState::Lunging if lunge.lunge.just_finished() => {
+                lunge.state = LungeState::Recover;
+                lunge.recover.reset();
+            }
+            LungeState::Recover if lunge.recover.just_finished() => {
+                lunge.state = LungeState::Approach;
+            }
+            LungeState::Lunging | LungeState::Recover => {}
+        }
+    }
+}
+
+fn tick_stuns(time: Res<Time>, mut stun_query: Query<&mut Stunned>) {
+    for mut stun in &mut stun_query {
+        stun.timer.tick(time.delta());
+    }
+}
+
+fn expire_stuns(mut commands: Commands, stun_query: Query<(Entity, &Stunned)>) {
+    for (entity, stun) in &stun_query {
+        if stun.timer.finished() {
+            commands.entity(entity).remove::<Stunned>();
+        }
+    }
+}
+
+/// Circles a few stars around the head of every stunned enemy.
+fn draw_stun_indicators(
+    mut gizmos: Gizmos,
+    time: Res<Time>,
+    stun_query: Query<&Transform, With<Stunned>>,
+) {
+    let spin = time.elapsed_seconds() * 6.0;
+    for transform in &stun_query {
+        let center = transform.translation.xy() + Vec2::new(0.0, 28.0);
+        for i in 0..3 {
+            let angle = spin + std::f32::consts::TAU * i as f32 / 3.0;
+            let star = center + Vec2::new(angle.cos() * 14.0, angle.sin() * 5.0);
+            gizmos.circle_2d(star, 2.5, Color::srgb(1.0, 0.95, 0.4));
+        }
+    }
+}
+
+fn reset_morale(mut morale: ResMut<EnemyMorale>) {
+    morale.broken = false;
+}
+
+/// Breaks the enemies' morale once their numbers drop below the threshold,
+/// and restores it once reinforcements bring them back up.
+fn morale_system(
+    mut commands: Commands,
+    mut morale: ResMut<EnemyMorale>,
+    enemy_query: Query<(), With<Enemy>>,
+    steering_query: Query<Entity, Or<(With<SnapToPlayer>, With<RotateToPlayer>)>>,
+) {
+    let enemy_count = enemy_query.iter().count();
+    if enemy_count >= morale.retreat_below {
+        morale.broken = false;
+        return;
+    }
+    if morale.broken || enemy_count == 0 {
+        return;
+    }
+
+    morale.broken = true;
+    for entity in &steering_query {
+        commands.entity(entity).insert(Retreating {
+            timer: Timer::from_seconds(morale.retreat_duration, TimerMode::Once),
+        });
+    }
+}
+
+fn tick_retreats(time: Res<Time>, mut retreat_query: Query<&mut Retreating>) {
+    for mut retreat in &mut retreat_query {
+        retreat.timer.tick(time.delta());
+    }
+}
+
+/// Lets retreating enemies regroup and turn back toward the player.
+fn expire_retreats(mut commands: Commands, retreat_query: Query<(Entity, &Retreating)>) {
+    for (entity, retreat) in &retreat_query {
+        if retreat.timer.finished() {
+            commands.entity(entity).remove::<Retreating>();
+        }
+    }
+}
+
+fn tick_trail_hazards(time: Res<Time>, mut query: Query<&mut TrailHazard>) {
+    for mut trail in &mut query {
+        trail.spawn_timer.tick(time.delta());
+    }
+}
+
+/// Drops a hazard tile at the position of each [`TrailHazard`] enemy whenever its timer fires.
+fn spawn_hazard_tile(mut commands: Commands, query: Query<(&TrailHazard, &Transform, &Team)>) {
+    for (trail, transform, team) in &query {
+        if trail.spawn_timer.just_finished() {
+            commands.spawn((
+                Name::new("Hazard Tile"),
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::srgba(0.9, 0.2, 0.1, 0.5),
+                        custom_size: Some(Vec2::splat(30.0)),
+                        ..default()
+                    },
+                    // keep the tiles underneath the ships
+                    transform: Transform::from_translation(transform.translation.xy().extend(-1.0)),
+                    ..default()
+                },
+                HazardTile {
+                    dps: 60.0,
+                    timer: Timer::from_seconds(3.0, TimerMode::Once),
+                },
+                Ignites {
+                    dps: 6.0,
+                    duration: 2.0,
+                    radius: 20.0,
+                },
+                // tiles fight for whoever dropped them
+                *team,
+                StateScoped(Screen::Playing),
+            ));
+        }
+    }
+}
+
+fn tick_hazard_tiles(time: Res<Time>, mut query: Query<&mut HazardTile>) {
+    for mut tile in &mut query {
+        tile.timer.tick(time.delta());
+    }
+}
+
+/// Despawns hazard tiles once they have expired.
+fn despawn_expired_hazard_tiles(mut commands: Commands, query: Query<(Entity, &HazardTile)>) {
+    for (entity, tile) in &query {
+        if tile.timer.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    #[test]
+    fn initial_spawn_stays_within_budget() {
+        let cheapest = InitialSpawn::default()
+            .weights
+            .iter()
+            .map(|(kind, _)| kind.cost())
+            .min()
+            .unwrap();
+        for budget in [0, 1, 2, 7, 20] {
+            let initial_spawn = InitialSpawn {
+                budget,
+                ..default()
+            };
+            for seed in 0..200 {
+                let kinds = initial_spawn.pick(&mut StdRng::seed_from_u64(seed));
+                let spent: u32 = kinds.iter().map(|kind| kind.cost()).sum();
+                assert!(spent <= budget, "seed {seed} spent {spent} of {budget}");
+                // picking only stops once nothing else fits
+                assert!(
+                    budget - spent < cheapest,
+                    "seed {seed} stopped at {spent} of {budget}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn later_waves_have_more_health() {
+        let scaling = EnemyHealthScaling::default();
+
+        assert_eq!(scaling.multiplier(1), 1.0);
+        assert!(scaling.multiplier(10) > scaling.multiplier(1));
+        // capped however late the wave
+        assert_eq!(scaling.multiplier(1000), scaling.max_multiplier);
+    }
+
+    /// An app stepping a fixed 100 ms per update, with the clock already started.
+    fn steering_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        let step = Duration::from_millis(100);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(step));
+        app.init_resource::<AiUpdateRate>();
+        app.add_systems(
+            Update,
+            (
+                enemy_movement_system,
+                snap_to_player_system,
+                rotate_to_player_system,
+            )
+                .chain(),
+        );
+        app.update();
+        app
+    }
+
+    /// Spawns an enemy at `position` facing up, turning toward the player.
+    fn spawn_rotating_enemy(app: &mut App, position: Vec2) -> Entity {
+        app.world_mut()
+            .spawn((
+                Enemy,
+                Transform::from_translation(position.extend(0.0)),
+                RotateToPlayer {
+                    rotation_speed: f32::to_radians(90.0),
+                },
+            ))
+            .id()
+    }
+
+    #[test]
+    fn enemy_outside_aggro_radius_holds_its_heading() {
+        let mut app = steering_app();
+        app.world_mut().spawn((Player, Transform::default()));
+        let far = spawn_rotating_enemy(&mut app, Vec2::new(500.0, 0.0));
+        app.world_mut().entity_mut(far).insert(AggroRadius(100.0));
+        let near = spawn_rotating_enemy(&mut app, Vec2::new(50.0, 0.0));
+        app.world_mut().entity_mut(near).insert(AggroRadius(100.0));
+
+        app.update();
+
+        let rotation = |entity| app.world().get::<Transform>(entity).unwrap().rotation;
+        assert_eq!(rotation(far), Quat::IDENTITY);
+        assert_ne!(rotation(near), Quat::IDENTITY);
+    }
+
+    #[test]
+    fn stunned_enemy_does_not_move_or_turn() {
+        let mut app = steering_app();
+        app.world_mut()
+            .spawn((Player, Transform::from_xyz(200.0, 0.0, 0.0)));
+        let stun = || Stunned {
+            timer: Timer::from_seconds(10.0, TimerMode::Once),
+        };
+        let rotating = spawn_rotating_enemy(&mut app, Vec2::ZERO);
+        app.world_mut().entity_mut(rotating).insert((
+            EnemyMove {
+                movement_speed: 100.0,
+            },
+            stun(),
+        ));
+        let snapping = app
+            .world_mut()
+            .spawn((
+                Enemy,
+                Transform::from_xyz(0.0, 100.0, 0.0),
+                SnapToPlayer::default(),
+                EnemyMove {
+                    movement_speed: 100.0,
+                },
+                stun(),
+            ))
+            .id();
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        let transform = |entity| *app.world().get::<Transform>(entity).unwrap();
+        assert_eq!(transform(rotating), Transform::default());
+        assert_eq!(transform(snapping), Transform::from_xyz(0.0, 100.0, 0.0));
+    }
+
+    #[test]
+    fn smooth_snap_eases_toward_the_player() {
+        let mut app = steering_app();
+        app.world_mut()
+            .spawn((Player, Transform::from_xyz(200.0, 0.0, 0.0)));
+        let snap = |app: &mut App, instant| {
+            app.world_mut()
+                .spawn((Enemy, Transform::default(), SnapToPlayer { instant }))
+                .id()
+        };
+        let instant = snap(&mut app, true);
+        let smooth = snap(&mut app, false);
+
+        app.update();
+
+        let turned = |app: &App, entity| {
+            let transform = app.world().get::<Transform>(entity).unwrap();
+            (transform.rotation * Vec3::Y)
+                .xy()
+                .angle_between(Vec2::Y)
+                .abs()
+        };
+        assert!((turned(&app, instant) - f32::to_radians(90.0)).abs() < 1e-4);
+        let eased = turned(&app, smooth);
+        assert!(eased > 0.0 && eased < f32::to_radians(90.0));
+
+        // it keeps closing the gap until it faces the player
+        for _ in 0..10 {
+            app.update();
+        }
+        assert!((turned(&app, smooth) - f32::to_radians(90.0)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn systems_skip_before_the_player_exists() {
+        let mut app = steering_app();
+        app.add_systems(Update, (face_player_on_spawn, lunge_system));
+        spawn_rotating_enemy(&mut app, Vec2::ZERO);
+        app.world_mut().spawn((
+            Enemy,
+            Transform::default(),
+            Sprite::default(),
+            SnapToPlayer::default(),
+            Lunge::default(),
+        ));
+
+        // no player has spawned, so the systems return early instead of panicking
+        app.update();
+        app.update();
+    }
+
+    #[test]
+    fn only_new_enemies_face_the_player_at_once() {
+        let mut app = steering_app();
+        app.add_systems(Update, face_player_on_spawn.before(rotate_to_player_system));
+        app.world_mut()
+            .spawn((Player, Transform::from_xyz(200.0, 0.0, 0.0)));
+        let spawned = spawn_rotating_enemy(&mut app, Vec2::ZERO);
+        // an enemy that was already around, and only starts tracking now
+        let alerted = app
+            .world_mut()
+            .spawn((Enemy, Transform::from_xyz(0.0, 100.0, 0.0)))
+            .id();
+        app.update();
+        app.world_mut().entity_mut(alerted).insert(RotateToPlayer {
+            rotation_speed: f32::to_radians(90.0),
+        });
+
+        app.update();
+
+        let forward = |entity| {
+            let transform = app.world().get::<Transform>(entity).unwrap();
+            (transform.rotation * Vec3::Y).xy()
+        };
+        assert!(forward(spawned).abs_diff_eq(Vec2::X, 1e-5));
+        // a 100 ms step at 90 degrees per second only turns it 9 degrees
+        let turned = forward(alerted).angle_between(Vec2::Y).abs();
+        assert!((turned - f32::to_radians(9.0)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn fixed_timestep_moves_enemies_once_per_fixed_step() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        let step = Duration::from_millis(50);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(step));
+        app.insert_resource(Time::<Fixed>::from_seconds(0.1));
+        app.insert_resource(State::new(Screen::Playing));
+        app.insert_resource(TimestepMode::Fixed);
+        app.init_resource::<AiUpdateRate>();
+        app.init_resource::<FacePlayerOnSpawn>();
+        app.add_systems(
+            Update,
+            steering_systems().run_if(resource_equals(TimestepMode::Variable)),
+        );
+        app.add_systems(
+            FixedUpdate,
+            steering_systems().run_if(resource_equals(TimestepMode::Fixed)),
+        );
+        // the first update only starts the clock
+        app.update();
+
+        let enemy = app
+            .world_mut()
+            .spawn((
+                Enemy,
+                Transform::default(),
+                EnemyMove {
+                    movement_speed: 100.0,
+                },
+            ))
+            .id();
+        let position = |app: &App| app.world().get::<Transform>(enemy).unwrap().translation;
+
+        // half a fixed step has passed, so the enemy hasn't moved yet
+        app.update();
+        assert_eq!(position(&app), Vec3::ZERO);
+
+        // a whole fixed step moves it by the fixed delta
+        app.update();
+        assert!(position(&app).abs_diff_eq(Vec3::new(0.0, 10.0, 0.0), 1e-4));
+    }
+}
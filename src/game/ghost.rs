@@ -0,0 +1,254 @@
+//! A translucent ghost that replays the player's longest run, to race against.
+
+use std::{fs, io};
+
+use bevy::prelude::*;
+
+use super::{
+    enemy::RunTime,
+    hud::{HudAnchor, HudElement},
+    ship::SelectedShip,
+    single_or_skip,
+    spawn::player::Player,
+};
+use crate::{screen::Screen, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(GhostRecording, BestGhost, Ghost)>();
+    app.init_resource::<GhostRecording>();
+    app.add_systems(Startup, load_best_ghost);
+    app.add_systems(
+        OnEnter(Screen::Playing),
+        (reset_ghost_recording, spawn_ghost),
+    );
+    app.add_systems(OnExit(Screen::Playing), keep_best_ghost);
+    app.add_systems(
+        Update,
+        (record_ghost_frame, drive_ghost, update_ghost_indicator)
+            .in_set(AppSet::Update)
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+/// Where the best run is saved between sessions.
+const GHOST_PATH: &str = "best_run.ghost";
+/// Seconds between recorded frames. The ghost interpolates between them.
+const GHOST_SAMPLE_INTERVAL: f32 = 0.05;
+
+/// One sampled player transform.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct GhostFrame {
+    /// Seconds since the start of the run.
+    pub time: f32,
+    pub position: Vec2,
+    /// Rotation around Z, in radians.
+    pub rotation: f32,
+}
+
+/// The player's transform over the current run.
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(Resource)]
+pub struct GhostRecording {
+    pub frames: Vec<GhostFrame>,
+}
+
+impl GhostRecording {
+    /// How long the recorded run lasted.
+    pub fn duration(&self) -> f32 {
+        self.frames.last().map_or(0.0, |frame| frame.time)
+    }
+
+    /// Position and rotation at `time`, interpolated between the surrounding frames.
+    /// `None` once the recording has ended.
+    fn sample(&self, time: f32) -> Option<(Vec2, f32)> {
+        let next = self.frames.iter().position(|frame| frame.time >= time)?;
+        let to = self.frames[next];
+        let Some(from) = next.checked_sub(1).map(|i| self.frames[i]) else {
+            return Some((to.position, to.rotation));
+        };
+
+        let t = (time - from.time) / (to.time - from.time);
+        let rotation = Quat::from_rotation_z(from.rotation)
+            .slerp(Quat::from_rotation_z(to.rotation), t)
+            .to_euler(EulerRot::XYZ)
+            .2;
+        Some((from.position.lerp(to.position, t), rotation))
+    }
+
+    /// Saves the recording as one `time x y rotation` line per frame.
+    fn save(&self) -> io::Result<()> {
+        let lines: Vec<_> = self
+            .frames
+            .iter()
+            .map(|frame| {
+                format!(
+                    "{} {} {} {}",
+                    frame.time, frame.position.x, frame.position.y, frame.rotation
+                )
+            })
+            .collect();
+        fs::write(GHOST_PATH, lines.join("\n"))
+    }
+
+    fn load() -> io::Result<Self> {
+        let frames = fs::read_to_string(GHOST_PATH)?
+            .lines()
+            .filter_map(|line| {
+                let values: Vec<f32> = line
+                    .split_whitespace()
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()
+                    .ok()?;
+                let [time, x, y, rotation] = values[..] else {
+                    return None;
+                };
+                Some(GhostFrame {
+                    time,
+                    position: Vec2::new(x, y),
+                    rotation,
+                })
+            })
+            .collect();
+        Ok(Self { frames })
+    }
+}
+
+/// The longest run so far, replayed by the [`Ghost`].
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(Resource)]
+pub struct BestGhost(pub GhostRecording);
+
+/// The ship replaying the [`BestGhost`]. It has no health or team, so nothing collides with it.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Ghost;
+
+/// Text telling the player whether they are ahead of the ghost.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct GhostIndicator;
+
+fn load_best_ghost(mut commands: Commands) {
+    let best = match GhostRecording::load() {
+        Ok(recording) => recording,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => default(),
+        Err(error) => {
+            warn!("Failed to load the best run ghost: {error}");
+            default()
+        }
+    };
+    commands.insert_resource(BestGhost(best));
+}
+
+fn reset_ghost_recording(mut recording: ResMut<GhostRecording>) {
+    recording.frames.clear();
+}
+
+/// Replaces the best run with the run that just ended if it lasted longer.
+fn keep_best_ghost(recording: Res<GhostRecording>, mut best: ResMut<BestGhost>) {
+    if recording.duration() <= best.0.duration() {
+        return;
+    }
+
+    best.0 = recording.clone();
+    if let Err(error) = best.0.save() {
+        warn!("Failed to save the best run ghost: {error}");
+    }
+}
+
+fn spawn_ghost(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    selected_ship: Res<SelectedShip>,
+    best: Res<BestGhost>,
+) {
+    if best.0.frames.is_empty() {
+        return;
+    }
+
+    let ship = selected_ship.stats();
+    commands.spawn((
+        Name::new("Ghost"),
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgba(0.7, 0.85, 1.0, 0.35),
+                ..default()
+            },
+            texture: asset_server.load(ship.texture),
+            // keep the ghost underneath the real ships
+            transform: Transform::from_xyz(0.0, 0.0, -0.2).with_scale(Vec3::splat(ship.scale)),
+            ..default()
+        },
+        Ghost,
+        StateScoped(Screen::Playing),
+    ));
+
+    commands.spawn((
+        Name::new("Ghost Indicator"),
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 24.0,
+                ..default()
+            },
+        ),
+        HudElement(HudAnchor::TopRight),
+        GhostIndicator,
+        StateScoped(Screen::Playing),
+    ));
+}
+
+fn record_ghost_frame(
+    run_time: Res<RunTime>,
+    mut recording: ResMut<GhostRecording>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let Some(transform) = single_or_skip(&player_query) else {
+        return;
+    };
+
+    let time = run_time.elapsed_secs();
+    let last_time = recording.frames.last().map(|frame| frame.time);
+    if last_time.is_some_and(|last_time| time - last_time < GHOST_SAMPLE_INTERVAL) {
+        return;
+    }
+
+    recording.frames.push(GhostFrame {
+        time,
+        position: transform.translation.xy(),
+        rotation: transform.rotation.to_euler(EulerRot::XYZ).2,
+    });
+}
+
+/// Moves the ghost along the best run, and removes it once that run is over.
+fn drive_ghost(
+    mut commands: Commands,
+    run_time: Res<RunTime>,
+    best: Res<BestGhost>,
+    mut ghost_query: Query<(Entity, &mut Transform), With<Ghost>>,
+) {
+    for (entity, mut transform) in &mut ghost_query {
+        match best.0.sample(run_time.elapsed_secs()) {
+            Some((position, rotation)) => {
+                transform.translation = position.extend(transform.translation.z);
+                transform.rotation = Quat::from_rotation_z(rotation);
+            }
+            None => commands.entity(entity).despawn(),
+        }
+    }
+}
+
+fn update_ghost_indicator(
+    run_time: Res<RunTime>,
+    best: Res<BestGhost>,
+    mut indicator_query: Query<&mut Text, With<GhostIndicator>>,
+) {
+    let remaining = best.0.duration() - run_time.elapsed_secs();
+    for mut text in &mut indicator_query {
+        text.sections[0].value = if remaining > 0.0 {
+            format!("Behind ghost: {remaining:.1}s to go")
+        } else {
+            "Beating ghost!".to_string()
+        };
+    }
+}
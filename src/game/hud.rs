@@ -0,0 +1,267 @@
+//! The in-game HUD layout. HUD elements pick a corner or edge of the screen with a
+//! [`HudElement`] and are laid out there by flexbox, so they keep to their anchor and don't
+//! overlap whatever the shape of the window.
+
+use bevy::{prelude::*, ui::Val::*};
+
+use crate::{screen::Screen, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(HudAnchor, HudElement, HudSlot)>();
+    app.add_systems(OnEnter(Screen::Playing), setup_hud);
+    app.add_systems(
+        Update,
+        attach_hud_elements
+            .in_set(AppSet::Update)
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+/// Where on the screen a [`HudElement`] goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum HudAnchor {
+    TopLeft,
+    TopCenter,
+    TopRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+impl HudAnchor {
+    const TOP: [Self; 3] = [Self::TopLeft, Self::TopCenter, Self::TopRight];
+    const BOTTOM: [Self; 3] = [Self::BottomLeft, Self::BottomCenter, Self::BottomRight];
+
+    /// How the elements in this anchor line up across the width of its slot.
+    fn align_items(self) -> AlignItems {
+        match self {
+            Self::TopLeft | Self::BottomLeft => AlignItems::FlexStart,
+            Self::TopCenter | Self::BottomCenter => AlignItems::Center,
+            Self::TopRight | Self::BottomRight => AlignItems::FlexEnd,
+        }
+    }
+
+    /// Elements stack away from the edge of the screen they are anchored to.
+    fn flex_direction(self) -> FlexDirection {
+        match self {
+            Self::TopLeft | Self::TopCenter | Self::TopRight => FlexDirection::Column,
+            _ => FlexDirection::ColumnReverse,
+        }
+    }
+}
+
+/// A UI node that is moved into the HUD slot of its anchor once the HUD exists.
+/// Spawn it without a parent and without absolute positioning.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct HudElement(pub HudAnchor);
+
+/// The node holding every [`HudElement`] of one anchor.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+struct HudSlot(HudAnchor);
+
+/// Spawns a full-screen root with a top and a bottom row of three equally wide slots each.
+fn setup_hud(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("HUD"),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Percent(100.0),
+                    height: Percent(100.0),
+                    padding: UiRect::all(Percent(1.5)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::SpaceBetween,
+                    ..default()
+                },
+                ..default()
+            },
+            StateScoped(Screen::Playing),
+        ))
+        .with_children(|hud| {
+            for row in [HudAnchor::TOP, HudAnchor::BOTTOM] {
+                hud.spawn((
+                    Name::new("HUD Row"),
+                    NodeBundle {
+                        style: Style {
+                            width: Percent(100.0),
+                            flex_direction: FlexDirection::Row,
+                            align_items: if row == HudAnchor::TOP {
+                                AlignItems::FlexStart
+                            } else {
+                                AlignItems::FlexEnd
+                            },
+                            ..default()
+                        },
+                        ..default()
+                    },
+                ))
+                .with_children(|row_node| {
+                    for anchor in row {
+                        row_node.spawn((
+                            Name::new(format!("HUD Slot {anchor:?}")),
+                            NodeBundle {
+                                style: Style {
+                                    // the slots split the row evenly, so the centre slot
+                                    // stays centred however wide the side slots' contents are
+                                    flex_basis: Px(0.0),
+                                    flex_grow: 1.0,
+                                    flex_direction: anchor.flex_direction(),
+                                    align_items: anchor.align_items(),
+                                    row_gap: Px(8.0),
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            HudSlot(anchor),
+                        ));
+                    }
+                });
+            }
+        });
+}
+
+/// Moves new HUD elements into the slot of their anchor.
+fn attach_hud_elements(
+    mut commands: Commands,
+    element_query: Query<(Entity, &HudElement), Without<Parent>>,
+    slot_query: Query<(Entity, &HudSlot)>,
+) {
+    for (element, HudElement(anchor)) in &element_query {
+        let Some((slot, _)) = slot_query.iter().find(|(_, slot)| slot.0 == *anchor) else {
+            continue;
+        };
+        commands.entity(slot).add_child(element);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        audio::AudioPlugin,
+        ecs::system::RunSystemOnce,
+        log::LogPlugin,
+        render::{
+            settings::{RenderCreation, WgpuSettings},
+            RenderPlugin,
+        },
+        window::{PrimaryWindow, WindowResized, WindowResolution},
+        winit::WinitPlugin,
+    };
+
+    use super::*;
+
+    /// A headless app running the real UI layout, with a primary window of the given size.
+    fn hud_app(width: f32, height: f32) -> App {
+        let mut app = App::new();
+        app.add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: Some(Window {
+                        resolution: WindowResolution::new(width, height)
+                            .with_scale_factor_override(1.0),
+                        ..default()
+                    }),
+                    ..default()
+                })
+                .set(RenderPlugin {
+                    render_creation: RenderCreation::Automatic(WgpuSettings {
+                        backends: None,
+                        ..default()
+                    }),
+                    ..default()
+                })
+                .disable::<WinitPlugin>()
+                .disable::<LogPlugin>()
+                .disable::<AudioPlugin>(),
+        );
+        app.add_systems(Update, attach_hud_elements);
+        app.world_mut().spawn(Camera2dBundle::default());
+        app.world_mut().run_system_once(setup_hud);
+        app
+    }
+
+    fn resize_window(app: &mut App, width: f32, height: f32) {
+        let mut query = app
+            .world_mut()
+            .query_filtered::<Entity, With<PrimaryWindow>>();
+        let window = query.single(app.world());
+        let mut window_ref = app.world_mut().get_mut::<Window>(window).unwrap();
+        window_ref.resolution.set(width, height);
+        app.world_mut().send_event(WindowResized {
+            window,
+            width,
+            height,
+        });
+    }
+
+    /// Runs the app until the layout matches the window. The layout isn't ordered after the
+    /// camera picks up the window size, so it may only catch up on the next frame.
+    fn update_layout(app: &mut App) {
+        app.update();
+        app.update();
+    }
+
+    /// Screen-space rectangle of the HUD element anchored to `anchor`.
+    fn element_rect(app: &mut App, anchor: HudAnchor) -> Rect {
+        let mut query = app
+            .world_mut()
+            .query::<(&Node, &GlobalTransform, &HudElement)>();
+        let (node, transform, _) = query
+            .iter(app.world())
+            .find(|(_, _, element)| element.0 == anchor)
+            .unwrap();
+        node.logical_rect(transform)
+    }
+
+    /// Checks each corner element sits in its corner, inset by the HUD padding.
+    fn assert_in_corners(app: &mut App, width: f32, height: f32) {
+        // percentage padding is relative to the width, on every side
+        let padding = width * 0.015;
+        let near = |a: f32, b: f32| (a - b).abs() < 0.5;
+
+        let top_left = element_rect(app, HudAnchor::TopLeft);
+        assert!(near(top_left.min.x, padding) && near(top_left.min.y, padding));
+        let top_right = element_rect(app, HudAnchor::TopRight);
+        assert!(near(top_right.max.x, width - padding) && near(top_right.min.y, padding));
+        let bottom_left = element_rect(app, HudAnchor::BottomLeft);
+        assert!(near(bottom_left.min.x, padding) && near(bottom_left.max.y, height - padding));
+        let bottom_right = element_rect(app, HudAnchor::BottomRight);
+        assert!(near(bottom_right.max.x, width - padding));
+        assert!(near(bottom_right.max.y, height - padding));
+    }
+
+    #[test]
+    fn corner_elements_follow_the_window_edges() {
+        let mut app = hud_app(1200.0, 640.0);
+        for anchor in [
+            HudAnchor::TopLeft,
+            HudAnchor::TopRight,
+            HudAnchor::BottomLeft,
+            HudAnchor::BottomRight,
+        ] {
+            app.world_mut().spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Px(100.0),
+                        height: Px(20.0),
+                        ..default()
+                    },
+                    ..default()
+                },
+                HudElement(anchor),
+            ));
+        }
+
+        update_layout(&mut app);
+        assert_in_corners(&mut app, 1200.0, 640.0);
+
+        for (width, height) in [(1600.0, 900.0), (800.0, 1000.0)] {
+            resize_window(&mut app, width, height);
+            update_layout(&mut app);
+            assert_in_corners(&mut app, width, height);
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//! Gameplay actions, gathered once per frame from the keyboard, mouse and gamepads.
+//! Gameplay systems read the [`ActionState`] instead of raw input, so bindings and
+//! devices only need to be handled here. Menus are navigated by `ui_tools` instead.
+
+use bevy::{input::InputSystem, prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Action, KeyBindings, ActionState)>();
+    app.init_resource::<KeyBindings>();
+    app.init_resource::<ActionState>();
+    app.add_systems(PreUpdate, gather_input.after(InputSystem));
+}
+
+/// Something the player can do, independent of the button it is bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum Action {
+    MoveForward,
+    TurnLeft,
+    TurnRight,
+    Fire,
+    RadarPing,
+    /// Slows the ship down for precise movement while held.
+    Focus,
+    /// Rewinds the ship back along its recent path.
+    Rewind,
+    /// Drops an acid pool under the ship.
+    DeployAcid,
+    /// Spins the ship to hit everything close around it.
+    SpinAttack,
+    /// Skips the tutorial.
+    Skip,
+    /// Leaves the run for the title screen.
+    ReturnToTitle,
+    TogglePhotoMode,
+    /// Moves the free camera in photo mode.
+    PanUp,
+    PanDown,
+    PanLeft,
+    PanRight,
+    /// Drags the free camera in photo mode along with the cursor while held.
+    DragCamera,
+    /// Dev builds only.
+    ToggleWorldGrid,
+    ToggleDensityMap,
+    SelectEnemy,
+}
+
+impl Action {
+    const ALL: [Self; 20] = [
+        Self::MoveForward,
+        Self::TurnLeft,
+        Self::TurnRight,
+        Self::Fire,
+        Self::RadarPing,
+        Self::Focus,
+        Self::Rewind,
+        Self::DeployAcid,
+        Self::SpinAttack,
+        Self::Skip,
+        Self::ReturnToTitle,
+        Self::TogglePhotoMode,
+        Self::PanUp,
+        Self::PanDown,
+        Self::PanLeft,
+        Self::PanRight,
+        Self::DragCamera,
+        Self::ToggleWorldGrid,
+        Self::ToggleDensityMap,
+        Self::SelectEnemy,
+    ];
+}
+
+/// The keys, mouse buttons and gamepad buttons bound to each [`Action`].
+/// An action can have several bindings, and a button can be bound to several actions.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct KeyBindings {
+    pub keys: Vec<(Action, KeyCode)>,
+    pub mouse_buttons: Vec<(Action, MouseButton)>,
+    pub gamepad_buttons: Vec<(Action, GamepadButtonType)>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            keys: vec![
+                (Action::MoveForward, KeyCode::KeyW),
+                (Action::MoveForward, KeyCode::ArrowUp),
+                (Action::TurnLeft, KeyCode::KeyA),
+                (Action::TurnLeft, KeyCode::ArrowLeft),
+                (Action::TurnRight, KeyCode::KeyD),
+                (Action::TurnRight, KeyCode::ArrowRight),
+                (Action::Fire, KeyCode::Space),
+                (Action::RadarPing, KeyCode::KeyR),
+                (Action::Focus, KeyCode::ShiftLeft),
+                (Action::Focus, KeyCode::ShiftRight),
+                (Action::Rewind, KeyCode::KeyF),
+                (Action::DeployAcid, KeyCode::KeyE),
+                (Action::SpinAttack, KeyCode::KeyQ),
+                (Action::Skip, KeyCode::Enter),
+                (Action::ReturnToTitle, KeyCode::Escape),
+                (Action::TogglePhotoMode, KeyCode::KeyP),
+                (Action::PanUp, KeyCode::KeyW),
+                (Action::PanDown, KeyCode::KeyS),
+                (Action::PanLeft, KeyCode::KeyA),
+                (Action::PanRight, KeyCode::KeyD),
+                (Action::ToggleWorldGrid, KeyCode::KeyG),
+                (Action::ToggleDensityMap, KeyCode::KeyH),
+            ],
+            mouse_buttons: vec![
+                (Action::Fire, MouseButton::Left),
+                (Action::DragCamera, MouseButton::Right),
+                (Action::SelectEnemy, MouseButton::Middle),
+            ],
+            gamepad_buttons: vec![
+                (Action::Fire, GamepadButtonType::RightTrigger2),
+                (Action::RadarPing, GamepadButtonType::North),
+                (Action::Focus, GamepadButtonType::LeftTrigger2),
+                (Action::Rewind, GamepadButtonType::West),
+                (Action::DeployAcid, GamepadButtonType::East),
+                (Action::SpinAttack, GamepadButtonType::South),
+                (Action::Skip, GamepadButtonType::Start),
+                (Action::ReturnToTitle, GamepadButtonType::Select),
+            ],
+        }
+    }
+}
+
+/// This frame's actions, along with the analog movement and rotation they add up to.
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(Resource)]
+pub struct ActionState {
+    buttons: ButtonInput<Action>,
+    /// Forward movement in `[-1, 1]`. Only analog sticks can move backward.
+    pub movement: f32,
+    /// Rotation in `[-1, 1]`, positive counter clockwise.
+    pub rotation: f32,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.buttons.pressed(action)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.buttons.just_pressed(action)
+    }
+
+    /// Whether any action at all was pressed this frame.
+    pub fn any_just_pressed(&self) -> bool {
+        self.buttons.get_just_pressed().next().is_some()
+    }
+}
+
+/// Run condition that is true on the frame `action` is pressed,
+/// like [`input_just_pressed`](bevy::input::common_conditions::input_just_pressed).
+pub fn action_just_pressed(action: Action) -> impl Fn(Res<ActionState>) -> bool + Clone {
+    move |actions: Res<ActionState>| actions.just_pressed(action)
+}
+
+fn gather_input(
+    bindings: Res<KeyBindings>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    mut state: ResMut<ActionState>,
+) {
+    let mut pressed: Vec<Action> = bindings
+        .keys
+        .iter()
+        .filter(|(_, key)| keyboard.pressed(*key))
+        .map(|(action, _)| *action)
+        .collect();
+    pressed.extend(
+        bindings
+            .mouse_buttons
+            .iter()
+            .filter(|(_, button)| mouse_buttons.pressed(*button))
+            .map(|(action, _)| *action),
+    );
+    for gamepad in gamepads.iter() {
+        pressed.extend(
+            bindings
+                .gamepad_buttons
+                .iter()
+                .filter(|(_, button)| gamepad_buttons.pressed(GamepadButton::new(gamepad, *button)))
+                .map(|(action, _)| *action),
+        );
+    }
+
+    // update the buttons so `just_pressed` only fires on the first frame of a press
+    state.buttons.clear();
+    for action in Action::ALL {
+        if pressed.contains(&action) {
+            state.buttons.press(action);
+        } else {
+            state.buttons.release(action);
+        }
+    }
+
+    let mut movement = 0.0;
+    if state.pressed(Action::MoveForward) {
+        movement += 1.0;
+    }
+
+    let mut rotation = 0.0;
+    if state.pressed(Action::TurnLeft) {
+        rotation += 1.0;
+    }
+    if state.pressed(Action::TurnRight) {
+        rotation -= 1.0;
+    }
+
+    // Add analog input from the left stick. Pushing right turns clockwise.
+    for gamepad in gamepads.iter() {
+        let axis = |axis_type| {
+            gamepad_axes
+                .get(GamepadAxis::new(gamepad, axis_type))
+                .unwrap_or_default()
+        };
+        movement += axis(GamepadAxisType::LeftStickY);
+        rotation -= axis(GamepadAxisType::LeftStickX);
+    }
+
+    state.movement = movement.clamp(-1.0, 1.0);
+    state.rotation = rotation.clamp(-1.0, 1.0);
+}
@@ -0,0 +1,110 @@
+//! The opening of a run: the player's ship flies in from below the playfield before
+//! control is handed over.
+
+use bevy::prelude::*;
+
+use super::{input::ActionState, single_or_skip_mut, spawn::player::Player};
+use crate::{camera::BOUNDS, screen::Screen};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_sub_state::<Intro>();
+    app.register_type::<IntroFlight>();
+    app.add_systems(OnEnter(Intro::Running), start_intro);
+    app.add_systems(
+        Update,
+        (fly_in, skip_intro)
+            .chain()
+            .run_if(in_state(Intro::Running)),
+    );
+}
+
+/// How long the ship takes to reach the centre.
+const INTRO_SECONDS: f32 = 1.5;
+/// How far below the playfield's bottom edge the ship starts.
+const START_MARGIN: f32 = 100.0;
+
+/// Whether the intro is still playing. Every run starts with it, and every
+/// [`AppSet`](crate::AppSet) is paused until it is done, so the player has no control and
+/// enemies stay put while the ship flies in.
+#[derive(SubStates, Debug, Hash, PartialEq, Eq, Clone, Copy, Default)]
+#[source(Screen = Screen::Playing)]
+pub enum Intro {
+    #[default]
+    Running,
+    Done,
+}
+
+/// The ship's flight from `from` to `to` during the intro.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct IntroFlight {
+    from: Vec2,
+    to: Vec2,
+    timer: Timer,
+}
+
+/// Slows down toward the end, so the ship glides into place.
+fn ease_out_cubic(t: f32) -> f32 {
+    1.0 - (1.0 - t).powi(3)
+}
+
+fn start_intro(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &mut Transform), With<Player>>,
+) {
+    let Some((player, mut transform)) = single_or_skip_mut(&mut player_query) else {
+        return;
+    };
+
+    let to = transform.translation.xy();
+    let from = Vec2::new(to.x, -BOUNDS.y / 2.0 - START_MARGIN);
+    transform.translation = from.extend(transform.translation.z);
+    commands.entity(player).insert(IntroFlight {
+        from,
+        to,
+        timer: Timer::from_seconds(INTRO_SECONDS, TimerMode::Once),
+    });
+}
+
+fn fly_in(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut next_intro: ResMut<NextState<Intro>>,
+    mut flight_query: Query<(Entity, &mut IntroFlight, &mut Transform)>,
+) {
+    let Some((player, mut flight, mut transform)) = single_or_skip_mut(&mut flight_query) else {
+        // nothing to fly in, so don't hold the run back
+        next_intro.set(Intro::Done);
+        return;
+    };
+
+    flight.timer.tick(time.delta());
+    let t = ease_out_cubic(flight.timer.fraction());
+    transform.translation = flight
+        .from
+        .lerp(flight.to, t)
+        .extend(transform.translation.z);
+
+    if flight.timer.finished() {
+        commands.entity(player).remove::<IntroFlight>();
+        next_intro.set(Intro::Done);
+    }
+}
+
+/// Pressing any action lands the ship right away.
+fn skip_intro(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    mut next_intro: ResMut<NextState<Intro>>,
+    mut flight_query: Query<(Entity, &IntroFlight, &mut Transform)>,
+) {
+    if !actions.any_just_pressed() {
+        return;
+    }
+
+    for (player, flight, mut transform) in &mut flight_query {
+        transform.translation = flight.to.extend(transform.translation.z);
+        commands.entity(player).remove::<IntroFlight>();
+    }
+    next_intro.set(Intro::Done);
+}
@@ -0,0 +1,182 @@
+//! Juggernaut enemies that lumber toward the player and periodically wind up a charge.
+
+use bevy::prelude::*;
+
+use super::{
+    combat::{ContactBehavior, Health, Team},
+    enemy::{Enemy, EnemyKind},
+    single_or_skip,
+    spawn::player::Player,
+};
+use crate::{camera::BOUNDS, screen::Screen, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Juggernaut>();
+    app.add_systems(OnEnter(Screen::Playing), spawn_juggernauts);
+    app.add_systems(
+        Update,
+        (
+            tick_juggernauts.in_set(AppSet::TickTimers),
+            (juggernaut_system, draw_juggernaut_telegraphs).in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+const JUGGERNAUT_COLOR: Color = Color::srgb(0.55, 0.6, 0.7);
+const WINDUP_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+
+/// What a [`Juggernaut`] is currently doing.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+enum JuggernautState {
+    /// Walking slowly toward the player until the cooldown fires.
+    Roaming,
+    /// Standing still and telegraphing a charge at where the player was when it started.
+    WindingUp { target: Vec2 },
+    /// Dashing in a straight line until the charge timer finishes.
+    Charging { direction: Vec2 },
+}
+
+/// A slow, tough enemy. Every `cooldown` it stops, locks onto the player's position for the
+/// length of `windup`, and then charges along that line at `charge_speed` for the length of
+/// `charge`. The charge never follows the player once it is locked.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Juggernaut {
+    /// Walking speed while roaming.
+    pub speed: f32,
+    pub charge_speed: f32,
+    pub cooldown: Timer,
+    pub windup: Timer,
+    pub charge: Timer,
+    state: JuggernautState,
+}
+
+impl Juggernaut {
+    pub fn new(speed: f32, charge_speed: f32) -> Self {
+        Self {
+            speed,
+            charge_speed,
+            cooldown: Timer::from_seconds(4.0, TimerMode::Once),
+            windup: Timer::from_seconds(1.0, TimerMode::Once),
+            charge: Timer::from_seconds(0.6, TimerMode::Once),
+            state: JuggernautState::Roaming,
+        }
+    }
+}
+
+fn spawn_juggernauts(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        Name::new("Juggernaut"),
+        SpriteBundle {
+            sprite: Sprite {
+                color: JUGGERNAUT_COLOR,
+                ..default()
+            },
+            texture: asset_server.load("enemy_B.png"),
+            transform: Transform::from_xyz(-BOUNDS.x * 0.4, 0.0, 0.0)
+                .with_rotation(Quat::from_rotation_z(-std::f32::consts::FRAC_PI_2))
+                .with_scale(Vec3::splat(1.5)),
+            ..default()
+        },
+        Juggernaut::new(40.0, 700.0),
+        // too heavy to pass through the player
+        ContactBehavior::Bounce,
+        Enemy,
+        EnemyKind::Juggernaut,
+        Team::Hostile,
+        Health {
+            value: EnemyKind::Juggernaut.base_health(),
+        },
+        StateScoped(Screen::Playing),
+    ));
+}
+
+fn tick_juggernauts(time: Res<Time>, mut juggernaut_query: Query<&mut Juggernaut>) {
+    for mut juggernaut in &mut juggernaut_query {
+        let timer = match juggernaut.state {
+            JuggernautState::Roaming => &mut juggernaut.cooldown,
+            JuggernautState::WindingUp { .. } => &mut juggernaut.windup,
+            JuggernautState::Charging { .. } => &mut juggernaut.charge,
+        };
+        timer.tick(time.delta());
+    }
+}
+
+/// Moves juggernauts according to their state, and moves them on to the next state
+/// once the timer of the current one fires.
+fn juggernaut_system(
+    time: Res<Time>,
+    player_query: Query<&Transform, With<Player>>,
+    mut juggernaut_query: Query<(&mut Juggernaut, &mut Transform, &mut Sprite), Without<Player>>,
+) {
+    let Some(player_transform) = single_or_skip(&player_query) else {
+        return;
+    };
+    let player_translation = player_transform.translation.xy();
+
+    for (mut juggernaut, mut transform, mut sprite) in &mut juggernaut_query {
+        let position = transform.translation.xy();
+
+        let velocity = match juggernaut.state {
+            JuggernautState::Roaming if juggernaut.cooldown.just_finished() => {
+                juggernaut.state = JuggernautState::WindingUp {
+                    target: player_translation,
+                };
+                juggernaut.windup.reset();
+                Vec2::ZERO
+            }
+            JuggernautState::Roaming => {
+                let to_player = (player_translation - position).normalize_or_zero();
+                transform.rotation = Quat::from_rotation_arc(Vec3::Y, to_player.extend(0.0));
+                to_player * juggernaut.speed
+            }
+            JuggernautState::WindingUp { target } if juggernaut.windup.just_finished() => {
+                let direction = (target - position).normalize_or_zero();
+                juggernaut.state = JuggernautState::Charging { direction };
+                juggernaut.charge.reset();
+                sprite.color = JUGGERNAUT_COLOR;
+                direction * juggernaut.charge_speed
+            }
+            JuggernautState::WindingUp { target } => {
+                let to_target = (target - position).normalize_or_zero();
+                transform.rotation = Quat::from_rotation_arc(Vec3::Y, to_target.extend(0.0));
+
+                // pulse between the base colour and the warning colour
+                let pulse = (juggernaut.windup.elapsed_secs() * 12.0).sin() * 0.5 + 0.5;
+                sprite.color = JUGGERNAUT_COLOR.mix(&WINDUP_COLOR, pulse);
+                Vec2::ZERO
+            }
+            JuggernautState::Charging { .. } if juggernaut.charge.just_finished() => {
+                juggernaut.state = JuggernautState::Roaming;
+                juggernaut.cooldown.reset();
+                Vec2::ZERO
+            }
+            JuggernautState::Charging { direction } => direction * juggernaut.charge_speed,
+        };
+
+        // bound the juggernaut within the level, so a charge stops at the edge
+        let destination = position + velocity * time.delta_seconds();
+        let clamped = destination.clamp(-BOUNDS / 2.0, BOUNDS / 2.0);
+        transform.translation = clamped.extend(transform.translation.z);
+    }
+}
+
+/// Draws a line to the locked target of every winding-up juggernaut, brightening as it winds up.
+fn draw_juggernaut_telegraphs(
+    mut gizmos: Gizmos,
+    juggernaut_query: Query<(&Juggernaut, &Transform)>,
+) {
+    for (juggernaut, transform) in &juggernaut_query {
+        let JuggernautState::WindingUp { target } = juggernaut.state else {
+            continue;
+        };
+
+        let alpha = 0.2 + 0.8 * juggernaut.windup.fraction();
+        gizmos.line_2d(
+            transform.translation.xy(),
+            target,
+            WINDUP_COLOR.with_alpha(alpha),
+        );
+    }
+}
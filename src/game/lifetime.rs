@@ -0,0 +1,51 @@
+//! Entities that despawn on their own after a while, such as short-lived effects.
+
+use bevy::prelude::*;
+
+use crate::AppSet;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Lifetime>();
+    app.add_systems(
+        Update,
+        (
+            tick_lifetimes.in_set(AppSet::TickTimers),
+            despawn_expired.in_set(AppSet::Update),
+        ),
+    );
+}
+
+/// Despawns the entity once `timer` finishes, wherever it is.
+/// Effects can read `timer.fraction()` to fade out over their lifetime.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Lifetime {
+    pub timer: Timer,
+}
+
+impl Lifetime {
+    pub fn from_seconds(seconds: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+        }
+    }
+
+    /// How far through its lifetime the entity is, from 0 to 1.
+    pub fn fraction(&self) -> f32 {
+        self.timer.fraction()
+    }
+}
+
+fn tick_lifetimes(time: Res<Time>, mut lifetime_query: Query<&mut Lifetime>) {
+    for mut lifetime in &mut lifetime_query {
+        lifetime.timer.tick(time.delta());
+    }
+}
+
+fn despawn_expired(mut commands: Commands, lifetime_query: Query<(Entity, &Lifetime)>) {
+    for (entity, lifetime) in &lifetime_query {
+        if lifetime.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
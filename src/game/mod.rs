@@ -1,11 +1,199 @@
 //! Game mechanics and content.
 
-use bevy::prelude::*;
+use std::{panic::Location, sync::Mutex};
+
+use bevy::{
+    ecs::query::{QueryData, QueryFilter, QuerySingleError, ROQueryItem},
+    prelude::*,
+};
 
 pub mod audio;
+pub mod bestiary;
+mod blinker;
+mod charger;
+mod collision;
+mod combat;
+mod difficulty;
+pub mod enemy;
+mod ghost;
+mod hud;
+pub mod input;
+pub mod intro;
+mod juggernaut;
+mod lifetime;
 mod movement;
+mod player;
+mod portal;
+mod powerup;
+mod protector;
+mod radar;
+mod rewind;
+mod sentinel;
+pub mod ship;
 pub mod spawn;
+mod starfield;
+pub mod stats;
+mod tutorial;
+mod weapon;
+pub mod xp;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((audio::plugin, movement::plugin, spawn::plugin));
+    app.register_type::<TimestepMode>();
+    app.init_resource::<TimestepMode>();
+
+    app.add_plugins((
+        audio::plugin,
+        bestiary::plugin,
+        combat::plugin,
+        difficulty::plugin,
+        input::plugin,
+        lifetime::plugin,
+        movement::plugin,
+        player::plugin,
+        powerup::plugin,
+        radar::plugin,
+        rewind::plugin,
+        ship::plugin,
+        spawn::plugin,
+        stats::plugin,
+        weapon::plugin,
+    ));
+
+    // The opening of a run.
+    app.add_plugins(intro::plugin);
+
+    // Progression within a run.
+    app.add_plugins(xp::plugin);
+
+    // Enemies and the portals that spawn them.
+    app.add_plugins((
+        blinker::plugin,
+        charger::plugin,
+        collision::plugin,
+        enemy::plugin,
+        juggernaut::plugin,
+        portal::plugin,
+        protector::plugin,
+        sentinel::plugin,
+    ));
+
+    // Visuals and guides that don't affect the gameplay.
+    app.add_plugins((
+        ghost::plugin,
+        hud::plugin,
+        starfield::plugin,
+        tutorial::plugin,
+    ));
+}
+
+/// Whether enemy movement, enemy steering and contact damage step once per frame, or at the
+/// fixed rate of [`Time<Fixed>`] in `FixedUpdate`. Those systems read `Res<Time>` either way,
+/// which Bevy points at the frame's delta in `Update` and the fixed delta in `FixedUpdate`.
+/// Fixed steps play out the same at any frame rate, while variable steps move more smoothly.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub enum TimestepMode {
+    #[default]
+    Variable,
+    Fixed,
+}
+
+/// The single entity matched by `query`, or `None` if there are none or several,
+/// e.g. before the player has spawned or after it has died.
+/// Systems should return early on `None` rather than panic. Logs the first time each call
+/// site skips, along with where it was called from.
+#[track_caller]
+pub fn single_or_skip<'a, D: QueryData, F: QueryFilter>(
+    query: &'a Query<D, F>,
+) -> Option<ROQueryItem<'a, D>> {
+    let caller = Location::caller();
+    query
+        .get_single()
+        .inspect_err(|error| warn_skip(caller, error))
+        .ok()
+}
+
+/// Mutable version of [`single_or_skip`].
+#[track_caller]
+pub fn single_or_skip_mut<'a, D: QueryData, F: QueryFilter>(
+    query: &'a mut Query<D, F>,
+) -> Option<D::Item<'a>> {
+    let caller = Location::caller();
+    query
+        .get_single_mut()
+        .inspect_err(|error| warn_skip(caller, error))
+        .ok()
+}
+
+fn warn_skip(caller: &'static Location<'static>, error: &QuerySingleError) {
+    if first_skip(caller) {
+        warn!("Skipping the system at {caller}: {error}");
+    }
+}
+
+/// Whether this is the first skip at `caller`, so each call site only logs once.
+fn first_skip(caller: &'static Location<'static>) -> bool {
+    static SKIPPED: Mutex<Vec<&Location>> = Mutex::new(Vec::new());
+    let mut skipped = SKIPPED.lock().unwrap_or_else(|error| error.into_inner());
+    if skipped.contains(&caller) {
+        return false;
+    }
+    skipped.push(caller);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::spawn::player::Player;
+
+    /// How many times each helper found the single player.
+    #[derive(Resource, Default)]
+    struct Found(u32);
+
+    fn find_player(
+        mut found: ResMut<Found>,
+        query: Query<&Player>,
+        mut mut_query: Query<&mut Transform, With<Player>>,
+    ) {
+        found.0 += u32::from(single_or_skip(&query).is_some());
+        found.0 += u32::from(single_or_skip_mut(&mut mut_query).is_some());
+    }
+
+    fn found_after_update(app: &mut App) -> u32 {
+        app.world_mut().resource_mut::<Found>().0 = 0;
+        app.update();
+        app.world().resource::<Found>().0
+    }
+
+    #[test]
+    fn single_or_skip_only_finds_a_single_player() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.init_resource::<Found>();
+        app.add_systems(Update, find_player);
+
+        // before the player spawns
+        assert_eq!(found_after_update(&mut app), 0);
+
+        app.world_mut().spawn((Player, Transform::default()));
+        assert_eq!(found_after_update(&mut app), 2);
+
+        app.world_mut().spawn((Player, Transform::default()));
+        assert_eq!(found_after_update(&mut app), 0);
+    }
+
+    #[test]
+    fn each_call_site_warns_once() {
+        #[track_caller]
+        fn caller() -> &'static Location<'static> {
+            Location::caller()
+        }
+        let (first, second) = (caller(), caller());
+
+        assert!(first_skip(first));
+        assert!(first_skip(second));
+        assert!(!first_skip(first));
+        assert!(!first_skip(second));
+    }
 }
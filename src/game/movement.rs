@@ -5,24 +5,32 @@
 
 use std::time::Duration;
 
-use bevy::{prelude::*, window::PrimaryWindow};
+use bevy::prelude::*;
 
-use super::{audio::sfx::Sfx, spawn::player::Player};
-use crate::AppSet;
+use super::{
+    audio::sfx::Sfx,
+    input::{Action, ActionState},
+    rewind::Rewinding,
+    spawn::player::Player,
+};
+use crate::{camera::BOUNDS, AppSet};
 
 pub(super) fn plugin(app: &mut App) {
     // Record directional input as movement controls.
-    app.register_type::<MovementController>();
+    app.register_type::<(MovementController, Focused)>();
     app.add_systems(
         Update,
         record_movement_controller.in_set(AppSet::RecordInput),
     );
 
     // Apply movement based on controls.
-    app.register_type::<(Movement, WrapWithinWindow)>();
+    app.register_type::<(Movement, SpeedBuff, WrapWithinWindow)>();
     app.add_systems(
         Update,
-        (apply_movement, wrap_within_window)
+        (
+            apply_movement.run_if(any_movement_intent),
+            wrap_within_window,
+        )
             .chain()
             .in_set(AppSet::Update),
     );
@@ -38,6 +46,8 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
+/// Movement and rotation intents, each in `[-1, 1]`.
+/// Keyboard input maps to the extremes, analog sticks anywhere in between.
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
 pub struct MovementController {
@@ -45,29 +55,41 @@ pub struct MovementController {
     rotation_intent: f32,
 }
 
-fn record_movement_controller(
-    input: Res<ButtonInput<KeyCode>>,
-    mut controller_query: Query<&mut MovementController, With<Player>>,
-) {
-    // Collect directional input.
-    let mut movement_intent = 0.0;
-    if input.pressed(KeyCode::KeyW) || input.pressed(KeyCode::ArrowUp) {
-        movement_intent += 1.0;
+impl MovementController {
+    pub fn movement_intent(&self) -> f32 {
+        self.movement_intent
     }
 
-    let mut rotation_intent = 0.0;
-    if input.pressed(KeyCode::KeyA) || input.pressed(KeyCode::ArrowLeft) {
-        rotation_intent += 1.0;
+    pub fn rotation_intent(&self) -> f32 {
+        self.rotation_intent
     }
+}
 
-    if input.pressed(KeyCode::KeyD) || input.pressed(KeyCode::ArrowRight) {
-        rotation_intent -= 1.0;
-    }
+/// Slows the ship down to [`FOCUS_SPEED_MULTIPLIER`] of its speed for precise movement.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Focused;
+
+/// Movement and rotation speed multiplier while [`Focused`].
+const FOCUS_SPEED_MULTIPLIER: f32 = 0.4;
+
+fn record_movement_controller(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    mut controller_query: Query<(Entity, &mut MovementController, Has<Focused>), With<Player>>,
+) {
+    let focus = actions.pressed(Action::Focus);
 
     // Apply movement intent to controllers.
-    for mut controller in &mut controller_query {
-        controller.movement_intent = movement_intent;
-        controller.rotation_intent = rotation_intent;
+    for (entity, mut controller, focused) in &mut controller_query {
+        controller.movement_intent = actions.movement;
+        controller.rotation_intent = actions.rotation;
+
+        if focus && !focused {
+            commands.entity(entity).insert(Focused);
+        } else if !focus && focused {
+            commands.entity(entity).remove::<Focused>();
+        }
     }
 }
 
@@ -78,22 +100,51 @@ pub struct Movement {
     pub rotation_speed: f32,
 }
 
+/// Run condition that skips [`apply_movement`] while every controller is idle.
+fn any_movement_intent(controller_query: Query<&MovementController>) -> bool {
+    controller_query
+        .iter()
+        .any(|controller| controller.movement_intent != 0.0 || controller.rotation_intent != 0.0)
+}
+
+/// Temporarily multiplies the movement speed. Removed once `timer` finishes.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct SpeedBuff {
+    pub timer: Timer,
+    pub multiplier: f32,
+}
+
 fn apply_movement(
     time: Res<Time>,
-    mut movement_query: Query<(&MovementController, &Movement, &mut Transform)>,
+    // a rewinding ship follows its history instead
+    mut movement_query: Query<
+        (
+            &MovementController,
+            &Movement,
+            Option<&SpeedBuff>,
+            Has<Focused>,
+            &mut Transform,
+        ),
+        Without<Rewinding>,
+    >,
 ) {
-    for (controller, movement, mut transform) in &mut movement_query {
+    for (controller, movement, speed_buff, focused, mut transform) in &mut movement_query {
+        let focus_multiplier = if focused { FOCUS_SPEED_MULTIPLIER } else { 1.0 };
+        let movement_speed = movement.movement_speed
+            * speed_buff.map_or(1.0, |buff| buff.multiplier)
+            * focus_multiplier;
+        let rotation_speed = movement.rotation_speed * focus_multiplier;
+
         // update the ship rotation around the Z axis (perpendicular to the 2D plane of the screen)
-        transform
-            .rotate_z(controller.rotation_intent * movement.rotation_speed * time.delta_seconds());
+        transform.rotate_z(controller.rotation_intent * rotation_speed * time.delta_seconds());
 
         // get the ship's forward vector by applying the current rotation to the ships initial facing
         // vector
         let movement_direction = transform.rotation * Vec3::Y;
         // get the distance the ship will move based on direction, the ship's movement speed and delta
         // time
-        let movement_distance =
-            controller.movement_intent * movement.movement_speed * time.delta_seconds();
+        let movement_distance = controller.movement_intent * movement_speed * time.delta_seconds();
         // create the change in translation using the new movement direction and distance
         let translation_delta = movement_direction * movement_distance;
         // update the ship translation with our new translation delta
@@ -101,15 +152,13 @@ fn apply_movement(
     }
 }
 
+/// Wrap around the edges of the playfield ([`BOUNDS`]), which the camera keeps in view.
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 pub struct WrapWithinWindow;
 
-fn wrap_within_window(
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    mut wrap_query: Query<&mut Transform, With<WrapWithinWindow>>,
-) {
-    let size = window_query.single().size() + 50.0;
+fn wrap_within_window(mut wrap_query: Query<&mut Transform, With<WrapWithinWindow>>) {
+    let size = BOUNDS + 50.0;
     let half_size = size / 2.0;
     for mut transform in &mut wrap_query {
         let position = transform.translation.xy();
@@ -151,3 +200,48 @@ fn trigger_step_sfx(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+
+    #[test]
+    fn focused_ship_moves_and_turns_slower() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        let step = Duration::from_millis(100);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(step));
+        app.add_systems(Update, apply_movement);
+        // the first update only starts the clock
+        app.update();
+
+        let mut spawn_ship = || {
+            app.world_mut()
+                .spawn((
+                    MovementController {
+                        movement_intent: 1.0,
+                        rotation_intent: 1.0,
+                    },
+                    Movement {
+                        movement_speed: 300.0,
+                        rotation_speed: 2.0,
+                    },
+                    Transform::default(),
+                ))
+                .id()
+        };
+        let normal = spawn_ship();
+        let focused = spawn_ship();
+        app.world_mut().entity_mut(focused).insert(Focused);
+
+        app.update();
+
+        let transform = |entity| *app.world().get::<Transform>(entity).unwrap();
+        let (normal, focused) = (transform(normal), transform(focused));
+        assert!(focused.translation.length() < normal.translation.length());
+        let turned = |transform: Transform| transform.rotation.angle_between(Quat::IDENTITY);
+        assert!(turned(focused) < turned(normal));
+    }
+}
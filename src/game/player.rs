@@ -0,0 +1,429 @@
+//! Player health, the repair kits that restore it, the phoenix revive and the low health warning.
+
+use bevy::prelude::*;
+use rand::prelude::*;
+
+use super::{
+    combat::{apply_damage, FriendlyFire, Health, MaxHealth, Team},
+    lifetime::Lifetime,
+    single_or_skip,
+    spawn::player::Player,
+};
+use crate::{camera::BOUNDS, screen::Screen, settings::Settings, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    // Spawn repair kits over time.
+    app.register_type::<(RepairKitTimer, RepairKit)>();
+    app.init_resource::<RepairKitTimer>();
+    app.add_systems(OnEnter(Screen::Playing), reset_repair_kit_timer);
+    app.add_systems(
+        Update,
+        (
+            tick_repair_kit_timer.in_set(AppSet::TickTimers),
+            spawn_repair_kit_system.in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+
+    // Pick up repair kits and channel the repair.
+    app.register_type::<Repairing>();
+    app.add_systems(
+        Update,
+        (
+            tick_repairs.in_set(AppSet::TickTimers),
+            (
+                pickup_repair_kit_system,
+                repair_system,
+                draw_repair_progress_system,
+            )
+                .chain()
+                .in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+
+    // Revive the player once when a phoenix buff is consumed.
+    app.register_type::<(Phoenix, ReviveBurst)>();
+    app.observe(revive_player);
+    app.add_systems(
+        Update,
+        draw_revive_bursts
+            .in_set(AppSet::Update)
+            .run_if(in_state(Screen::Playing)),
+    );
+
+    // Glow around the edges of the screen while health is critical.
+    app.register_type::<CriticalHealthGlow>();
+    app.add_systems(OnEnter(Screen::Playing), spawn_critical_health_glow);
+    app.add_systems(
+        Update,
+        critical_health_glow
+            .in_set(AppSet::Update)
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+/// Colour of repair kits and the repair progress ring.
+const REPAIR_COLOR: Color = Color::srgb(0.2, 0.9, 0.4);
+/// How long a repair takes to channel, in seconds.
+const REPAIR_DURATION: f32 = 1.5;
+
+/// Colour of the phoenix pickup and the revive burst.
+pub const PHOENIX_COLOR: Color = Color::srgb(1.0, 0.75, 0.2);
+/// Fraction of the ship's maximum health restored by a phoenix revive.
+const PHOENIX_REVIVE_HEALTH: f32 = 0.5;
+/// Opposing entities within this distance of the player are hit by the revive burst.
+const PHOENIX_BURST_RADIUS: f32 = 250.0;
+const PHOENIX_BURST_DAMAGE: f32 = 80.0;
+
+/// Fraction of the ship's maximum health below which the screen edges glow.
+const CRITICAL_HEALTH: f32 = 0.3;
+const CRITICAL_GLOW_COLOR: Color = Color::srgb(0.9, 0.05, 0.05);
+
+/// Time between repair kit spawns.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+struct RepairKitTimer(Timer);
+
+impl Default for RepairKitTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(15.0, TimerMode::Repeating))
+    }
+}
+
+/// Pickup that slowly repairs the player ship to full health.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct RepairKit;
+
+/// An in-progress repair, interrupted if the player takes damage.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Repairing {
+    /// Health when the repair started.
+    from: f32,
+    /// Fraction of the repair already healed, as of the last frame.
+    healed: f32,
+    timer: Timer,
+}
+
+/// Saves the player from the next lethal hit, once.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Phoenix;
+
+/// The player's [`Phoenix`] buff was consumed by lethal damage. Triggered on the player.
+#[derive(Event, Debug)]
+pub struct PhoenixRevived;
+
+/// The expanding ring shown where the player was revived.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ReviveBurst;
+
+/// The red border around the screen shown while health is critical.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct CriticalHealthGlow;
+
+fn reset_repair_kit_timer(mut timer: ResMut<RepairKitTimer>) {
+    timer.0.reset();
+}
+
+fn tick_repair_kit_timer(time: Res<Time>, mut timer: ResMut<RepairKitTimer>) {
+    timer.0.tick(time.delta());
+}
+
+fn spawn_repair_kit_system(
+    mut commands: Commands,
+    timer: Res<RepairKitTimer>,
+    kit_query: Query<(), With<RepairKit>>,
+) {
+    // only keep a single repair kit on the field at a time
+    if timer.0.just_finished() && kit_query.is_empty() {
+        let spawn_position = Vec3::new(
+            random::<f32>() * BOUNDS.x - BOUNDS.x / 2.,
+            random::<f32>() * BOUNDS.y - BOUNDS.y / 2.,
+            0.0,
+        );
+
+        commands.spawn((
+            Name::new("Repair Kit"),
+            SpriteBundle {
+                sprite: Sprite {
+                    color: REPAIR_COLOR,
+                    custom_size: Some(Vec2::splat(20.0)),
+                    ..default()
+                },
+                transform: Transform::from_translation(spawn_position),
+                ..default()
+            },
+            RepairKit,
+            Team::Neutral,
+            StateScoped(Screen::Playing),
+        ));
+    }
+}
+
+/// Starts a repair when the player ship touches a repair kit.
+fn pickup_repair_kit_system(
+    mut commands: Commands,
+    player_query: Query<(Entity, &Health, &Transform), (With<Player>, Without<Repairing>)>,
+    kit_query: Query<(Entity, &Transform), With<RepairKit>>,
+) {
+    let Some((player, health, player_transform)) = single_or_skip(&player_query) else {
+        return;
+    };
+
+    for (kit, kit_transform) in &kit_query {
+        let distance = player_transform
+            .translation
+            .distance(kit_transform.translation);
+
+        if distance < 30.0 {
+            commands.entity(kit).despawn();
+            commands.entity(player).insert(Repairing {
+                from: health.value,
+                healed: 0.0,
+                timer: Timer::from_seconds(REPAIR_DURATION, TimerMode::Once),
+            });
+            break;
+        }
+    }
+}
+
+fn tick_repairs(time: Res<Time>, mut repair_query: Query<&mut Repairing>) {
+    for mut repairing in &mut repair_query {
+        repairing.timer.tick(time.delta());
+    }
+}
+
+/// Regenerates player health toward its maximum over the repair duration.
+/// Only this frame's share is added, so a hit taken on the same frame isn't overwritten.
+fn repair_system(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &mut Health, &MaxHealth, &mut Repairing), With<Player>>,
+) {
+    for (player, mut health, max_health, mut repairing) in &mut player_query {
+        let missing = max_health.value - repairing.from;
+        let fraction = repairing.timer.fraction();
+        health.value =
+            (health.value + missing * (fraction - repairing.healed)).min(max_health.value);
+        repairing.healed = fraction;
+
+        if repairing.timer.finished() {
+            commands.entity(player).remove::<Repairing>();
+        }
+    }
+}
+
+/// Draws a ring around the player ship that fills up as the repair progresses.
+fn draw_repair_progress_system(
+    mut gizmos: Gizmos,
+    player_query: Query<(&Transform, &Repairing), With<Player>>,
+) {
+    for (transform, repairing) in &player_query {
+        let arc_angle = std::f32::consts::TAU * repairing.timer.fraction();
+        gizmos.arc_2d(
+            transform.translation.xy(),
+            0.0,
+            arc_angle,
+            40.0,
+            REPAIR_COLOR,
+        );
+    }
+}
+
+/// Brings the player back at part of its maximum health and blasts nearby enemies.
+fn revive_player(
+    trigger: Trigger<PhoenixRevived>,
+    mut commands: Commands,
+    friendly_fire: Res<FriendlyFire>,
+    mut player_query: Query<(&mut Health, &MaxHealth, &Transform, &Team), With<Player>>,
+    mut target_query: Query<(Entity, &mut Health, &Transform, &Team), Without<Player>>,
+) {
+    let Ok((mut health, max_health, player_transform, player_team)) =
+        player_query.get_mut(trigger.entity())
+    else {
+        return;
+    };
+    health.value = max_health.value * PHOENIX_REVIVE_HEALTH;
+    info!("Phoenix revive! Player health: {}", health.value);
+
+    let position = player_transform.translation.xy();
+    for (target, mut target_health, target_transform, target_team) in &mut target_query {
+        let in_range = target_transform.translation.xy().distance(position) < PHOENIX_BURST_RADIUS;
+        if in_range && target_team.is_hurt_by(*player_team, friendly_fire.0) {
+            apply_damage(
+                &mut commands,
+                target,
+                &mut target_health,
+                PHOENIX_BURST_DAMAGE,
+            );
+        }
+    }
+
+    commands.spawn((
+        Name::new("Revive Burst"),
+        SpatialBundle::from_transform(Transform::from_translation(position.extend(0.0))),
+        ReviveBurst,
+        Lifetime::from_seconds(0.6),
+        StateScoped(Screen::Playing),
+    ));
+}
+
+/// Draws rings racing out to the burst radius over the burst's lifetime.
+fn draw_revive_bursts(
+    mut gizmos: Gizmos,
+    burst_query: Query<(&Lifetime, &Transform), With<ReviveBurst>>,
+) {
+    for (lifetime, transform) in &burst_query {
+        // ease out so the rings slow down as they reach the edge of the burst
+        let fraction = lifetime.fraction();
+        let radius = PHOENIX_BURST_RADIUS * fraction.sqrt();
+        let color = PHOENIX_COLOR.with_alpha(1.0 - fraction);
+        for scale in [1.0, 0.8, 0.6] {
+            gizmos.circle_2d(transform.translation.xy(), radius * scale, color);
+        }
+    }
+}
+
+fn spawn_critical_health_glow(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Critical Health Glow"),
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                border: UiRect::all(Val::Px(24.0)),
+                ..default()
+            },
+            border_color: BorderColor(Color::NONE),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        CriticalHealthGlow,
+        StateScoped(Screen::Playing),
+    ));
+}
+
+/// Pulses the glow while the player's health is critical, brighter and faster the lower it is.
+/// With reduced motion on, the glow holds steady at its brightest instead of pulsing.
+fn critical_health_glow(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    player_query: Query<(&Health, &MaxHealth), With<Player>>,
+    mut glow_query: Query<(&mut Visibility, &mut BorderColor), With<CriticalHealthGlow>>,
+) {
+    let Some((health, max_health)) = single_or_skip(&player_query) else {
+        return;
+    };
+
+    let fraction = health.value / max_health.value;
+    for (mut visibility, mut border_color) in &mut glow_query {
+        if fraction >= CRITICAL_HEALTH {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        // 0 at the threshold, 1 at zero health
+        let severity = (1.0 - fraction / CRITICAL_HEALTH).clamp(0.0, 1.0);
+        let pulse = if settings.reduced_motion {
+            1.0
+        } else {
+            (time.elapsed_seconds() * (4.0 + 6.0 * severity)).sin() * 0.5 + 0.5
+        };
+        let alpha = (0.25 + 0.45 * severity) * (0.6 + 0.4 * pulse);
+        *visibility = Visibility::Visible;
+        border_color.0 = CRITICAL_GLOW_COLOR.with_alpha(alpha);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+
+    #[test]
+    fn repair_kit_heals_to_full_over_the_repair_duration() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        let step = Duration::from_millis(100);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(step));
+        app.add_systems(
+            Update,
+            (tick_repairs, pickup_repair_kit_system, repair_system).chain(),
+        );
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Transform::default(),
+                Health { value: 40.0 },
+                MaxHealth { value: 100.0 },
+            ))
+            .id();
+        let kit = app
+            .world_mut()
+            .spawn((RepairKit, Transform::from_xyz(10.0, 0.0, 0.0)))
+            .id();
+
+        // the first update only starts the clock, and picks up the kit
+        app.update();
+        assert!(app.world().get_entity(kit).is_none());
+        assert!(app.world().get::<Repairing>(player).is_some());
+
+        // 0.7 of the 1.5 second repair heals 0.7 / 1.5 of the missing 60 health
+        for _ in 0..7 {
+            app.update();
+        }
+        let health = |app: &App| app.world().get::<Health>(player).unwrap().value;
+        assert!((health(&app) - 68.0).abs() < 1e-3);
+
+        for _ in 0..8 {
+            app.update();
+        }
+        assert!((health(&app) - 100.0).abs() < 1e-3);
+        assert!(app.world().get::<Repairing>(player).is_none());
+    }
+
+    #[test]
+    fn critical_glow_holds_steady_with_reduced_motion() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        let step = Duration::from_millis(100);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(step));
+        app.insert_resource(Settings {
+            reduced_motion: true,
+            ..default()
+        });
+        app.add_systems(Update, critical_health_glow);
+        app.world_mut()
+            .spawn((Player, Health { value: 10.0 }, MaxHealth { value: 100.0 }));
+        let glow = app
+            .world_mut()
+            .spawn((
+                CriticalHealthGlow,
+                Visibility::Hidden,
+                BorderColor(Color::NONE),
+            ))
+            .id();
+
+        let mut alphas = Vec::new();
+        for _ in 0..5 {
+            app.update();
+            alphas.push(app.world().get::<BorderColor>(glow).unwrap().0.alpha());
+        }
+
+        assert!(alphas[0] > 0.0);
+        assert!(alphas.iter().all(|&alpha| alpha == alphas[0]));
+        assert_eq!(
+            app.world().get::<Visibility>(glow),
+            Some(&Visibility::Visible)
+        );
+    }
+}
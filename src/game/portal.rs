@@ -0,0 +1,275 @@
+//! Destructible portals that spawn enemies until the player shuts them down.
+//! Destroying every portal clears the wave and opens a larger set.
+//! Each wave's portals share a threat budget, and stop spawning once it is spent.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use rand::prelude::*;
+
+use super::{
+    combat::{Health, Team},
+    difficulty::{DifficultyCurve, RubberBand},
+    enemy::{spawn_enemy, Enemy, EnemyHealthScaling, RunTime, MAX_ENEMY_LEVEL},
+};
+use crate::{camera::BOUNDS, screen::Screen, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Portal, PortalWave, WaveConfig, WaveBudget)>();
+    app.init_resource::<PortalWave>();
+    app.init_resource::<WaveConfig>();
+    app.init_resource::<WaveBudget>();
+    app.add_systems(OnEnter(Screen::Playing), reset_portal_wave);
+    app.add_systems(
+        Update,
+        (
+            tick_portal_timers.in_set(AppSet::TickTimers),
+            (
+                portal_system,
+                despawn_destroyed_portals,
+                start_next_portal_wave,
+            )
+                .in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+/// Number of portals opened in the first wave. Each later wave opens one more.
+const FIRST_WAVE_PORTALS: u32 = 2;
+/// Starting health of every portal.
+const PORTAL_HEALTH: f32 = 120.0;
+
+/// Spawns an enemy every time its timer finishes.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Portal {
+    pub spawn_timer: Timer,
+}
+
+/// The current portal wave, starting at 0 before the first portals open.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct PortalWave(pub u32);
+
+/// How much threat each portal wave gets to spend on enemies.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct WaveConfig {
+    /// Threat budget of the first wave.
+    pub budget: u32,
+    /// Budget added for each wave after the first.
+    pub budget_growth: u32,
+}
+
+impl Default for WaveConfig {
+    fn default() -> Self {
+        Self {
+            budget: 12,
+            budget_growth: 6,
+        }
+    }
+}
+
+impl WaveConfig {
+    /// The threat budget of `wave`, counting from 1.
+    pub fn budget_for(&self, wave: u32) -> u32 {
+        self.budget + self.budget_growth * wave.saturating_sub(1)
+    }
+}
+
+/// What is left of the current wave's threat budget.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct WaveBudget {
+    pub remaining: u32,
+}
+
+impl WaveBudget {
+    /// Pays for an enemy of `level`, or of `base_level` if the budget doesn't cover it, and
+    /// returns the level paid for. Returns `None` without spending anything if neither fits.
+    fn spend(&mut self, level: u32, base_level: u32) -> Option<u32> {
+        let level = [level, base_level]
+            .into_iter()
+            .find(|&level| cost_for_level(level) <= self.remaining)?;
+        self.remaining -= cost_for_level(level);
+        Some(level)
+    }
+}
+
+/// How much of the [`WaveBudget`] a portal enemy of `level` uses up. Tougher enemies cost
+/// more, so a wave buys either a swarm of weak enemies or a few strong ones.
+pub fn cost_for_level(level: u32) -> u32 {
+    1 + 2 * level
+}
+
+fn reset_portal_wave(mut wave: ResMut<PortalWave>, mut budget: ResMut<WaveBudget>) {
+    wave.0 = 0;
+    budget.remaining = 0;
+}
+
+/// Ticks the portal timers, keeping their interval in line with the difficulty curve.
+fn tick_portal_timers(
+    time: Res<Time>,
+    run_time: Res<RunTime>,
+    curve: Res<DifficultyCurve>,
+    rubber_band: Res<RubberBand>,
+    mut portal_query: Query<&mut Portal>,
+) {
+    let difficulty = rubber_band.apply(curve.sample(run_time.elapsed_secs()));
+    let spawn_interval = Duration::from_secs_f32(difficulty.spawn_interval);
+    for mut portal in &mut portal_query {
+        portal.spawn_timer.set_duration(spawn_interval);
+        portal.spawn_timer.tick(time.delta());
+    }
+}
+
+/// Spawns an enemy at each portal whose timer just finished and pays for it from the
+/// [`WaveBudget`], unless the enemy limit of the difficulty curve, adjusted by the
+/// [`RubberBand`], is reached.
+fn portal_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    run_time: Res<RunTime>,
+    wave: Res<PortalWave>,
+    mut budget: ResMut<WaveBudget>,
+    health_scaling: Res<EnemyHealthScaling>,
+    curve: Res<DifficultyCurve>,
+    rubber_band: Res<RubberBand>,
+    enemy_query: Query<(), With<Enemy>>,
+    portal_query: Query<(&Portal, &Transform)>,
+) {
+    let difficulty = rubber_band.apply(curve.sample(run_time.elapsed_secs()));
+    let health_multiplier = health_scaling.multiplier(wave.0);
+    let mut enemy_count = enemy_query.iter().count();
+
+    for (portal, transform) in &portal_query {
+        if !portal.spawn_timer.just_finished() || enemy_count as f32 >= difficulty.max_enemies {
+            continue;
+        }
+
+        // elites come out one level above the rest, when the budget still covers them
+        let base_level = run_time.enemy_level();
+        let mut level = base_level;
+        if random::<f32>() < difficulty.elite_chance {
+            level = (level + 1).min(MAX_ENEMY_LEVEL);
+        }
+        let Some(level) = budget.spend(level, base_level) else {
+            continue;
+        };
+
+        spawn_enemy(
+            &mut commands,
+            &asset_server,
+            transform.translation.xy().extend(0.0),
+            level,
+            health_multiplier,
+            difficulty.speed_multiplier,
+        );
+        enemy_count += 1;
+    }
+}
+
+/// Removes portals whose health has run out. Unlike enemies, they don't count as kills.
+fn despawn_destroyed_portals(
+    mut commands: Commands,
+    portal_query: Query<(Entity, &Health), With<Portal>>,
+) {
+    for (entity, health) in &portal_query {
+        if health.value <= 0.0 {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Opens the next wave of portals once every portal of the current wave is destroyed.
+fn start_next_portal_wave(
+    mut commands: Commands,
+    mut wave: ResMut<PortalWave>,
+    config: Res<WaveConfig>,
+    mut budget: ResMut<WaveBudget>,
+    portal_query: Query<(), With<Portal>>,
+) {
+    if !portal_query.is_empty() {
+        return;
+    }
+
+    if wave.0 > 0 {
+        info!("Portal wave {} cleared", wave.0);
+    }
+    wave.0 += 1;
+    budget.remaining = config.budget_for(wave.0);
+
+    for _ in 0..FIRST_WAVE_PORTALS + wave.0 - 1 {
+        // keep the portals away from the edges so enemies don't spawn clamped to the bounds
+        let spawn_position = Vec3::new(
+            (random::<f32>() - 0.5) * BOUNDS.x * 0.8,
+            (random::<f32>() - 0.5) * BOUNDS.y * 0.8,
+            -0.5,
+        );
+
+        commands.spawn((
+            Name::new("Portal"),
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::srgb(0.6, 0.2, 0.9),
+                    custom_size: Some(Vec2::splat(48.0)),
+                    ..default()
+                },
+                transform: Transform::from_translation(spawn_position)
+                    .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4)),
+                ..default()
+            },
+            Portal {
+                spawn_timer: Timer::from_seconds(3.0, TimerMode::Repeating),
+            },
+            Health {
+                value: PORTAL_HEALTH,
+            },
+            Team::Hostile,
+            StateScoped(Screen::Playing),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    #[test]
+    fn waves_never_spend_more_than_their_budget() {
+        let config = WaveConfig::default();
+        for wave in 1..=5 {
+            let budget = config.budget_for(wave);
+            for seed in 0..200 {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let mut wave_budget = WaveBudget { remaining: budget };
+                let mut spent = 0;
+                // keep spawning random enemies, elites included, until one doesn't fit
+                loop {
+                    let base_level = rng.gen_range(0..=MAX_ENEMY_LEVEL);
+                    let level = (base_level + rng.gen_range(0..=1)).min(MAX_ENEMY_LEVEL);
+                    let Some(level) = wave_budget.spend(level, base_level) else {
+                        break;
+                    };
+                    spent += cost_for_level(level);
+                }
+                assert!(spent <= budget, "seed {seed} spent {spent} of {budget}");
+                assert_eq!(spent + wave_budget.remaining, budget);
+            }
+        }
+    }
+
+    #[test]
+    fn elites_fall_back_to_the_base_level_when_over_budget() {
+        let mut budget = WaveBudget {
+            remaining: cost_for_level(1),
+        };
+
+        assert_eq!(budget.spend(2, 1), Some(1));
+        assert_eq!(budget.spend(1, 0), None);
+        assert_eq!(budget.remaining, 0);
+    }
+}
@@ -0,0 +1,294 @@
+//! Powerups that temporarily buff the player ship.
+
+use bevy::prelude::*;
+use rand::prelude::*;
+
+use super::{
+    combat::Team,
+    lifetime::Lifetime,
+    movement::SpeedBuff,
+    player::{Phoenix, PHOENIX_COLOR},
+    single_or_skip,
+    spawn::player::Player,
+};
+use crate::{camera::BOUNDS, screen::Screen, settings::effects_enabled, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    // Spawn speed boosts over time.
+    app.register_type::<(SpeedBoostTimer, SpeedBoost)>();
+    app.init_resource::<SpeedBoostTimer>();
+    app.add_systems(OnEnter(Screen::Playing), reset_speed_boost_timer);
+    app.add_systems(
+        Update,
+        (
+            tick_speed_boost_timer.in_set(AppSet::TickTimers),
+            spawn_speed_boost_system.in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+
+    // Occasionally spawn a phoenix feather, which saves the player from one lethal hit.
+    app.register_type::<(PhoenixTimer, PhoenixFeather)>();
+    app.init_resource::<PhoenixTimer>();
+    app.add_systems(OnEnter(Screen::Playing), reset_phoenix_timer);
+    app.add_systems(
+        Update,
+        (
+            tick_phoenix_timer.in_set(AppSet::TickTimers),
+            (spawn_phoenix_feather_system, pickup_phoenix_feather_system).in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+
+    // Pick up speed boosts, and leave a trail while the buff lasts.
+    app.register_type::<SpeedTrail>();
+    app.add_systems(
+        Update,
+        (
+            tick_speed_buffs.in_set(AppSet::TickTimers),
+            (
+                pickup_speed_boost_system,
+                expire_speed_buffs,
+                spawn_speed_trail.run_if(effects_enabled),
+                fade_speed_trails,
+            )
+                .chain()
+                .in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+const SPEED_BOOST_COLOR: Color = Color::srgb(0.3, 0.8, 1.0);
+/// How long a speed boost lasts once picked up, in seconds.
+const SPEED_BUFF_DURATION: f32 = 5.0;
+const SPEED_BUFF_MULTIPLIER: f32 = 1.6;
+/// Seconds between trail sprites while the buff is active.
+const SPEED_TRAIL_INTERVAL: f32 = 0.04;
+
+/// Time between speed boost spawns.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+struct SpeedBoostTimer(Timer);
+
+impl Default for SpeedBoostTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(20.0, TimerMode::Repeating))
+    }
+}
+
+/// Pickup that gives the player ship a [`SpeedBuff`].
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct SpeedBoost;
+
+/// Chance of a phoenix feather spawning each time the [`PhoenixTimer`] finishes.
+const PHOENIX_SPAWN_CHANCE: f32 = 0.35;
+
+/// Time between chances of a phoenix feather spawning.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+struct PhoenixTimer(Timer);
+
+impl Default for PhoenixTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(30.0, TimerMode::Repeating))
+    }
+}
+
+/// Rare pickup that gives the player ship a [`Phoenix`] buff.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct PhoenixFeather;
+
+/// A fading afterimage left behind a buffed ship.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct SpeedTrail;
+
+fn reset_speed_boost_timer(mut timer: ResMut<SpeedBoostTimer>) {
+    timer.0.reset();
+}
+
+fn tick_speed_boost_timer(time: Res<Time>, mut timer: ResMut<SpeedBoostTimer>) {
+    timer.0.tick(time.delta());
+}
+
+fn spawn_speed_boost_system(
+    mut commands: Commands,
+    timer: Res<SpeedBoostTimer>,
+    boost_query: Query<(), With<SpeedBoost>>,
+) {
+    // only keep a single speed boost on the field at a time
+    if timer.0.just_finished() && boost_query.is_empty() {
+        let spawn_position = Vec3::new(
+            random::<f32>() * BOUNDS.x - BOUNDS.x / 2.,
+            random::<f32>() * BOUNDS.y - BOUNDS.y / 2.,
+            0.0,
+        );
+
+        commands.spawn((
+            Name::new("Speed Boost"),
+            SpriteBundle {
+                sprite: Sprite {
+                    color: SPEED_BOOST_COLOR,
+                    custom_size: Some(Vec2::splat(20.0)),
+                    ..default()
+                },
+                transform: Transform::from_translation(spawn_position)
+                    .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4)),
+                ..default()
+            },
+            SpeedBoost,
+            Team::Neutral,
+            StateScoped(Screen::Playing),
+        ));
+    }
+}
+
+/// Buffs the player ship when it touches a speed boost. Picking up another one refreshes the buff.
+fn pickup_speed_boost_system(
+    mut commands: Commands,
+    player_query: Query<(Entity, &Transform), With<Player>>,
+    boost_query: Query<(Entity, &Transform), With<SpeedBoost>>,
+) {
+    let Some((player, player_transform)) = single_or_skip(&player_query) else {
+        return;
+    };
+
+    for (boost, boost_transform) in &boost_query {
+        let distance = player_transform
+            .translation
+            .distance(boost_transform.translation);
+
+        if distance < 30.0 {
+            commands.entity(boost).despawn();
+            commands.entity(player).insert(SpeedBuff {
+                timer: Timer::from_seconds(SPEED_BUFF_DURATION, TimerMode::Once),
+                multiplier: SPEED_BUFF_MULTIPLIER,
+            });
+            break;
+        }
+    }
+}
+
+fn tick_speed_buffs(time: Res<Time>, mut buff_query: Query<&mut SpeedBuff>) {
+    for mut buff in &mut buff_query {
+        buff.timer.tick(time.delta());
+    }
+}
+
+fn expire_speed_buffs(mut commands: Commands, buff_query: Query<(Entity, &SpeedBuff)>) {
+    for (entity, buff) in &buff_query {
+        if buff.timer.finished() {
+            commands.entity(entity).remove::<SpeedBuff>();
+        }
+    }
+}
+
+/// Leaves an afterimage of every buffed ship at a fixed interval.
+fn spawn_speed_trail(
+    mut commands: Commands,
+    mut last_spawn: Local<f32>,
+    time: Res<Time>,
+    buff_query: Query<(&Transform, &Handle<Image>), With<SpeedBuff>>,
+) {
+    let now = time.elapsed_seconds();
+    if now - *last_spawn < SPEED_TRAIL_INTERVAL {
+        return;
+    }
+    *last_spawn = now;
+
+    for (transform, texture) in &buff_query {
+        commands.spawn((
+            Name::new("Speed Trail"),
+            SpriteBundle {
+                sprite: Sprite {
+                    color: SPEED_BOOST_COLOR.with_alpha(0.5),
+                    ..default()
+                },
+                texture: texture.clone(),
+                // keep the trail underneath the ships
+                transform: transform.with_translation(transform.translation.xy().extend(-0.1)),
+                ..default()
+            },
+            SpeedTrail,
+            Lifetime::from_seconds(0.3),
+            StateScoped(Screen::Playing),
+        ));
+    }
+}
+
+/// Fades trail sprites out over their lifetime.
+fn fade_speed_trails(mut trail_query: Query<(&Lifetime, &mut Sprite), With<SpeedTrail>>) {
+    for (lifetime, mut sprite) in &mut trail_query {
+        sprite.color.set_alpha(0.5 * (1.0 - lifetime.fraction()));
+    }
+}
+
+fn reset_phoenix_timer(mut timer: ResMut<PhoenixTimer>) {
+    timer.0.reset();
+}
+
+fn tick_phoenix_timer(time: Res<Time>, mut timer: ResMut<PhoenixTimer>) {
+    timer.0.tick(time.delta());
+}
+
+fn spawn_phoenix_feather_system(
+    mut commands: Commands,
+    timer: Res<PhoenixTimer>,
+    feather_query: Query<(), With<PhoenixFeather>>,
+    phoenix_query: Query<(), (With<Player>, With<Phoenix>)>,
+) {
+    // the buff doesn't stack, so don't offer another one while the player still has it
+    if !timer.0.just_finished() || !feather_query.is_empty() || !phoenix_query.is_empty() {
+        return;
+    }
+    if random::<f32>() > PHOENIX_SPAWN_CHANCE {
+        return;
+    }
+
+    let spawn_position = Vec3::new(
+        random::<f32>() * BOUNDS.x - BOUNDS.x / 2.,
+        random::<f32>() * BOUNDS.y - BOUNDS.y / 2.,
+        0.0,
+    );
+
+    commands.spawn((
+        Name::new("Phoenix Feather"),
+        SpriteBundle {
+            sprite: Sprite {
+                color: PHOENIX_COLOR,
+                custom_size: Some(Vec2::new(12.0, 28.0)),
+                ..default()
+            },
+            transform: Transform::from_translation(spawn_position),
+            ..default()
+        },
+        PhoenixFeather,
+        Team::Neutral,
+        StateScoped(Screen::Playing),
+    ));
+}
+
+fn pickup_phoenix_feather_system(
+    mut commands: Commands,
+    player_query: Query<(Entity, &Transform), With<Player>>,
+    feather_query: Query<(Entity, &Transform), With<PhoenixFeather>>,
+) {
+    let Some((player, player_transform)) = single_or_skip(&player_query) else {
+        return;
+    };
+
+    for (feather, feather_transform) in &feather_query {
+        let distance = player_transform
+            .translation
+            .distance(feather_transform.translation);
+
+        if distance < 30.0 {
+            commands.entity(feather).despawn();
+            commands.entity(player).insert(Phoenix);
+            break;
+        }
+    }
+}
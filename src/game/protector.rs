@@ -0,0 +1,133 @@
+//! Protector enemies that shield the enemies around them from the player's weapons.
+
+use bevy::prelude::*;
+
+use super::{
+    combat::{Health, Team},
+    enemy::{Enemy, EnemyKind, EnemyMove, RotateToPlayer},
+};
+use crate::{camera::BOUNDS, screen::Screen, settings::effects_enabled, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Protector, Protected)>();
+    app.add_systems(OnEnter(Screen::Playing), spawn_protectors);
+    app.add_systems(
+        Update,
+        (aura_system, draw_protector_auras.run_if(effects_enabled))
+            .chain()
+            .in_set(AppSet::Update)
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+const PROTECTOR_COLOR: Color = Color::srgb(0.4, 1.0, 0.7);
+
+/// An enemy that protects every other enemy within `radius` of it.
+/// It isn't protected by its own aura, so taking it out first removes the shield.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Protector {
+    pub radius: f32,
+    /// Fraction of the damage that protected enemies still take.
+    pub damage_taken: f32,
+}
+
+/// An enemy inside a [`Protector`]'s aura. The player's weapons only deal `damage_taken`
+/// of their damage to it. Kept up to date every frame by the aura system.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+pub struct Protected {
+    pub damage_taken: f32,
+}
+
+impl Protected {
+    /// Reduces `amount` when the target is protected.
+    pub fn apply(protected: Option<&Self>, amount: f32) -> f32 {
+        protected.map_or(amount, |protected| amount * protected.damage_taken)
+    }
+}
+
+fn spawn_protectors(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        Name::new("Protector"),
+        SpriteBundle {
+            sprite: Sprite {
+                color: PROTECTOR_COLOR,
+                ..default()
+            },
+            texture: asset_server.load("enemy_A.png"),
+            transform: Transform::from_xyz(0.0, -BOUNDS.y * 0.4, 0.0),
+            ..default()
+        },
+        Protector {
+            radius: 160.0,
+            damage_taken: 0.4,
+        },
+        // trails slowly behind the others
+        EnemyMove {
+            movement_speed: 60.0,
+        },
+        RotateToPlayer {
+            rotation_speed: f32::to_radians(60.0),
+        },
+        Enemy,
+        EnemyKind::Protector,
+        Team::Hostile,
+        Health {
+            value: EnemyKind::Protector.base_health(),
+        },
+        StateScoped(Screen::Playing),
+    ));
+}
+
+/// Tags enemies inside a protector's aura with [`Protected`], using the strongest aura
+/// they are in, and untags those that left every aura.
+fn aura_system(
+    mut commands: Commands,
+    protector_query: Query<(Entity, &Protector, &Transform)>,
+    enemy_query: Query<(Entity, &Transform, Option<&Protected>), With<Enemy>>,
+) {
+    for (enemy, transform, protected) in &enemy_query {
+        let position = transform.translation.xy();
+        let damage_taken = protector_query
+            .iter()
+            .filter(|(protector, aura, protector_transform)| {
+                *protector != enemy
+                    && protector_transform.translation.xy().distance(position) < aura.radius
+            })
+            .map(|(_, aura, _)| aura.damage_taken)
+            .reduce(f32::min);
+
+        match (damage_taken, protected) {
+            (Some(damage_taken), _) => {
+                let aura = Protected { damage_taken };
+                if protected != Some(&aura) {
+                    commands.entity(enemy).insert(aura);
+                }
+            }
+            (None, Some(_)) => {
+                commands.entity(enemy).remove::<Protected>();
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// Outlines each aura, and links it to the enemies it protects.
+fn draw_protector_auras(
+    mut gizmos: Gizmos,
+    protector_query: Query<(&Protector, &Transform)>,
+    protected_query: Query<&Transform, With<Protected>>,
+) {
+    for (protector, transform) in &protector_query {
+        let center = transform.translation.xy();
+        gizmos.circle_2d(center, protector.radius, PROTECTOR_COLOR.with_alpha(0.35));
+
+        for protected_transform in &protected_query {
+            let target = protected_transform.translation.xy();
+            if target.distance(center) < protector.radius {
+                gizmos.line_2d(center, target, PROTECTOR_COLOR.with_alpha(0.2));
+            }
+        }
+    }
+}
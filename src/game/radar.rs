@@ -0,0 +1,132 @@
+//! Radar ping that briefly reveals every enemy on the field.
+
+use bevy::prelude::*;
+
+use super::{
+    enemy::Enemy,
+    input::{Action, ActionState},
+    single_or_skip,
+    spawn::player::Player,
+};
+use crate::{camera::CameraZoom, screen::Screen, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<RadarPing>();
+    app.init_resource::<RadarPing>();
+    app.add_systems(OnEnter(Screen::Playing), reset_radar_ping);
+    app.add_systems(
+        Update,
+        (
+            tick_radar_ping.in_set(AppSet::TickTimers),
+            start_radar_ping.in_set(AppSet::RecordInput),
+            (draw_radar_rings, draw_enemy_markers).in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+const RADAR_COLOR: Color = Color::srgb(0.3, 1.0, 0.8);
+/// Radius the ping ring reaches when the ping ends.
+const RADAR_RANGE: f32 = 800.0;
+/// How far markers are kept inside the edge of the view.
+const MARKER_MARGIN: f32 = 24.0;
+
+/// The radar ping: enemies are revealed while `timer` runs,
+/// and another ping can't start until `cooldown` finishes.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct RadarPing {
+    pub timer: Timer,
+    pub cooldown: Timer,
+}
+
+impl Default for RadarPing {
+    fn default() -> Self {
+        let mut timer = Timer::from_seconds(1.0, TimerMode::Once);
+        let mut cooldown = Timer::from_seconds(6.0, TimerMode::Once);
+        // start finished so the ping is ready and hidden
+        timer.set_elapsed(timer.duration());
+        cooldown.set_elapsed(cooldown.duration());
+        Self { timer, cooldown }
+    }
+}
+
+impl RadarPing {
+    fn is_active(&self) -> bool {
+        !self.timer.finished()
+    }
+}
+
+fn reset_radar_ping(mut ping: ResMut<RadarPing>) {
+    *ping = RadarPing::default();
+}
+
+fn tick_radar_ping(time: Res<Time>, mut ping: ResMut<RadarPing>) {
+    ping.timer.tick(time.delta());
+    ping.cooldown.tick(time.delta());
+}
+
+fn start_radar_ping(actions: Res<ActionState>, mut ping: ResMut<RadarPing>) {
+    if actions.just_pressed(Action::RadarPing) && ping.cooldown.finished() {
+        ping.timer.reset();
+        ping.cooldown.reset();
+    }
+}
+
+/// Draws rings expanding out from the player while the ping is active.
+fn draw_radar_rings(
+    mut gizmos: Gizmos,
+    ping: Res<RadarPing>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    if !ping.is_active() {
+        return;
+    }
+    let Some(player_transform) = single_or_skip(&player_query) else {
+        return;
+    };
+
+    let fraction = ping.timer.fraction();
+    let color = RADAR_COLOR.with_alpha(1.0 - fraction);
+    // a second, trailing ring makes the pulse easier to read
+    for offset in [0.0, 0.15] {
+        let radius = (fraction - offset).max(0.0) * RADAR_RANGE;
+        gizmos.circle_2d(player_transform.translation.xy(), radius, color);
+    }
+}
+
+/// Marks every enemy while the ping is active.
+/// Enemies outside the view are marked at the nearest edge of the view instead.
+fn draw_enemy_markers(
+    mut gizmos: Gizmos,
+    ping: Res<RadarPing>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<CameraZoom>>,
+    enemy_query: Query<&Transform, With<Enemy>>,
+) {
+    if !ping.is_active() {
+        return;
+    }
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+
+    let center = camera_transform.translation.xy();
+    let view = Rect::from_center_size(center, projection.area.size()).inflate(-MARKER_MARGIN);
+    let color = RADAR_COLOR.with_alpha(1.0 - ping.timer.fraction());
+
+    for enemy_transform in &enemy_query {
+        let position = enemy_transform.translation.xy();
+        let marker = position.clamp(view.min, view.max);
+
+        if marker == position {
+            gizmos.circle_2d(position, 24.0, color);
+        } else {
+            // point the marker toward the enemy
+            let direction = (position - marker).normalize();
+            let side = direction.perp() * 8.0;
+            let tip = marker + direction * 12.0;
+            gizmos.line_2d(marker + side, tip, color);
+            gizmos.line_2d(marker - side, tip, color);
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! Rewind the player's ship back along the path it took over the last couple of seconds,
+//! restoring the health it had at the time.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use super::{
+    combat::{Health, Invulnerable},
+    input::{Action, ActionState},
+    spawn::player::Player,
+};
+use crate::{screen::Screen, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(PositionHistory, Rewinding, RewindCooldown)>();
+    app.add_systems(
+        Update,
+        (
+            (tick_position_history, tick_rewind_cooldowns).in_set(AppSet::TickTimers),
+            (
+                start_rewind,
+                record_position_history,
+                rewind_system,
+                expire_rewind_cooldowns,
+                draw_rewind_path,
+            )
+                .chain()
+                .in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+/// Seconds between two samples of the [`PositionHistory`].
+const SAMPLE_INTERVAL: f32 = 1.0 / 30.0;
+/// How many seconds of history are kept, and so how far back a rewind goes.
+const HISTORY_SECONDS: f32 = 2.0;
+/// How many samples a rewind steps back per sample interval.
+/// The full history plays back in `HISTORY_SECONDS / REWIND_SPEED` seconds.
+const REWIND_SPEED: usize = 2;
+/// Seconds after a rewind ends before the next one can start.
+const REWIND_COOLDOWN: f32 = 8.0;
+
+const REWIND_COLOR: Color = Color::srgb(0.5, 0.8, 1.0);
+
+/// Where the ship was and how much health it had at one point in its history.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+struct HistorySample {
+    translation: Vec3,
+    rotation: Quat,
+    health: f32,
+}
+
+/// The last [`HISTORY_SECONDS`] of the ship's transform and health, oldest first,
+/// sampled every [`SAMPLE_INTERVAL`] and consumed by a rewind.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct PositionHistory {
+    samples: VecDeque<HistorySample>,
+    sample_timer: Timer,
+}
+
+impl Default for PositionHistory {
+    fn default() -> Self {
+        Self {
+            samples: VecDeque::new(),
+            sample_timer: Timer::from_seconds(SAMPLE_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
+impl PositionHistory {
+    fn capacity() -> usize {
+        (HISTORY_SECONDS / SAMPLE_INTERVAL) as usize
+    }
+}
+
+/// Plays the ship's [`PositionHistory`] backward until it runs out.
+/// Movement input is ignored in the meantime.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Rewinding;
+
+/// Stops the ship from rewinding again until `timer` finishes.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct RewindCooldown {
+    pub timer: Timer,
+}
+
+fn tick_position_history(time: Res<Time>, mut history_query: Query<&mut PositionHistory>) {
+    for mut history in &mut history_query {
+        history.sample_timer.tick(time.delta());
+    }
+}
+
+fn tick_rewind_cooldowns(time: Res<Time>, mut cooldown_query: Query<&mut RewindCooldown>) {
+    for mut cooldown in &mut cooldown_query {
+        cooldown.timer.tick(time.delta());
+    }
+}
+
+fn start_rewind(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    player_query: Query<
+        (Entity, &PositionHistory),
+        (With<Player>, Without<Rewinding>, Without<RewindCooldown>),
+    >,
+) {
+    if !actions.just_pressed(Action::Rewind) {
+        return;
+    }
+
+    for (player, history) in &player_query {
+        if !history.samples.is_empty() {
+            commands.entity(player).insert((Rewinding, Invulnerable));
+        }
+    }
+}
+
+/// Samples the transform and health of every ship that isn't rewinding,
+/// dropping the oldest sample once the history is full.
+fn record_position_history(
+    mut history_query: Query<(&mut PositionHistory, &Transform, &Health), Without<Rewinding>>,
+) {
+    for (mut history, transform, health) in &mut history_query {
+        if !history.sample_timer.just_finished() {
+            continue;
+        }
+
+        if history.samples.len() >= PositionHistory::capacity() {
+            history.samples.pop_front();
+        }
+        history.samples.push_back(HistorySample {
+            translation: transform.translation,
+            rotation: transform.rotation,
+            health: health.value,
+        });
+    }
+}
+
+/// Steps rewinding ships back through their history, and ends the rewind once it is used up.
+fn rewind_system(
+    mut commands: Commands,
+    mut rewind_query: Query<
+        (Entity, &mut PositionHistory, &mut Transform, &mut Health),
+        With<Rewinding>,
+    >,
+) {
+    for (entity, mut history, mut transform, mut health) in &mut rewind_query {
+        if !history.sample_timer.just_finished() {
+            continue;
+        }
+
+        let mut sample = None;
+        for _ in 0..REWIND_SPEED {
+            sample = history.samples.pop_back().or(sample);
+        }
+        if let Some(sample) = sample {
+            transform.translation = sample.translation;
+            transform.rotation = sample.rotation;
+            health.value = sample.health;
+        }
+
+        if history.samples.is_empty() {
+            commands
+                .entity(entity)
+                .remove::<(Rewinding, Invulnerable)>()
+                .insert(RewindCooldown {
+                    timer: Timer::from_seconds(REWIND_COOLDOWN, TimerMode::Once),
+                });
+        }
+    }
+}
+
+fn expire_rewind_cooldowns(
+    mut commands: Commands,
+    cooldown_query: Query<(Entity, &RewindCooldown)>,
+) {
+    for (entity, cooldown) in &cooldown_query {
+        if cooldown.timer.finished() {
+            commands.entity(entity).remove::<RewindCooldown>();
+        }
+    }
+}
+
+/// Draws the path still to be rewound, from the ship back to where the rewind will end.
+fn draw_rewind_path(
+    mut gizmos: Gizmos,
+    rewind_query: Query<(&PositionHistory, &Transform), With<Rewinding>>,
+) {
+    for (history, transform) in &rewind_query {
+        let path = history
+            .samples
+            .iter()
+            .rev()
+            .map(|sample| sample.translation.xy());
+        gizmos.linestrip_2d(
+            std::iter::once(transform.translation.xy()).chain(path),
+            REWIND_COLOR.with_alpha(0.6),
+        );
+        if let Some(end) = history.samples.front() {
+            gizmos.circle_2d(end.translation.xy(), 12.0, REWIND_COLOR);
+        }
+    }
+}
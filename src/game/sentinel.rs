@@ -0,0 +1,221 @@
+//! Sentinel enemies that patrol until the player enters their vision cone.
+
+use bevy::prelude::*;
+
+use super::{
+    combat::{Health, Team},
+    enemy::{Enemy, EnemyKind, EnemyMove, RotateToPlayer, Stunned},
+    single_or_skip,
+    spawn::player::Player,
+};
+use crate::{camera::BOUNDS, screen::Screen, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Sentinel, Patrol)>();
+    app.add_systems(OnEnter(Screen::Playing), spawn_sentinels);
+    app.add_systems(
+        Update,
+        (vision_cone_system, patrol_system)
+            .chain()
+            .in_set(AppSet::Update)
+            .run_if(in_state(Screen::Playing)),
+    );
+
+    // Show what the sentinels can see in dev builds.
+    #[cfg(feature = "dev")]
+    app.add_systems(
+        Update,
+        draw_vision_cones
+            .in_set(AppSet::Update)
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+/// An enemy that only engages once it has seen the player.
+/// While alerted it steers like a [`RotateToPlayer`] enemy.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Sentinel {
+    /// Half of the vision cone's opening angle, in radians.
+    pub half_angle: f32,
+    /// How far the sentinel can see.
+    pub range: f32,
+    /// Steering used while alerted.
+    pub movement_speed: f32,
+    pub rotation_speed: f32,
+    /// How long the sentinel keeps chasing after losing sight of the player.
+    pub lose_sight_timer: Timer,
+    pub alerted: bool,
+}
+
+/// A route an unalerted [`Sentinel`] walks back and forth along, sweeping its vision cone
+/// over the playfield as it turns around at each end.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Patrol {
+    pub waypoints: [Vec2; 2],
+    /// Index of the waypoint being walked toward.
+    pub next: usize,
+    pub movement_speed: f32,
+    pub rotation_speed: f32,
+}
+
+/// How close a patrolling sentinel gets to a waypoint before turning to the other one.
+const WAYPOINT_RADIUS: f32 = 10.0;
+
+impl Sentinel {
+    /// Whether a target at `to_target` (relative to the sentinel) is inside the vision cone
+    /// of a sentinel facing `forward`.
+    pub fn can_see(&self, forward: Vec2, to_target: Vec2) -> bool {
+        let distance = to_target.length();
+        if distance > self.range {
+            return false;
+        }
+        if distance == 0.0 {
+            return true;
+        }
+
+        // both vectors are unit length, so the dot product is the cosine of the angle between them
+        forward.dot(to_target / distance) >= self.half_angle.cos()
+    }
+}
+
+fn spawn_sentinels(mut commands: Commands, asset_server: Res<AssetServer>) {
+    // one sentinel down each side, starting at the top and looking toward the center
+    for x in [-1.0, 1.0] {
+        let position = Vec2::new(x * BOUNDS.x * 0.4, BOUNDS.y * 0.35);
+        let rotation = Quat::from_rotation_arc(Vec3::Y, (-position).normalize().extend(0.0));
+        let bottom = Vec2::new(position.x, -BOUNDS.y * 0.35);
+
+        commands.spawn((
+            Name::new("Sentinel"),
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::srgb(0.5, 0.8, 1.0),
+                    ..default()
+                },
+                texture: asset_server.load("enemy_B.png"),
+                transform: Transform::from_translation(position.extend(0.0))
+                    .with_rotation(rotation),
+                ..default()
+            },
+            Sentinel {
+                half_angle: f32::to_radians(30.0),
+                range: 350.0,
+                movement_speed: 180.0,
+                rotation_speed: f32::to_radians(120.0),
+                lose_sight_timer: Timer::from_seconds(3.0, TimerMode::Once),
+                alerted: false,
+            },
+            Patrol {
+                waypoints: [bottom, position],
+                next: 0,
+                movement_speed: 60.0,
+                rotation_speed: f32::to_radians(90.0),
+            },
+            Enemy,
+            EnemyKind::Sentinel,
+            Team::Hostile,
+            Health {
+                value: EnemyKind::Sentinel.base_health(),
+            },
+            StateScoped(Screen::Playing),
+        ));
+    }
+}
+
+/// Alerts sentinels that can see the player, and calms them down again once they have lost
+/// sight of the player for long enough.
+fn vision_cone_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    player_query: Query<&Transform, With<Player>>,
+    mut sentinel_query: Query<(Entity, &mut Sentinel, &Transform)>,
+) {
+    let Some(player_transform) = single_or_skip(&player_query) else {
+        return;
+    };
+    let player_translation = player_transform.translation.xy();
+
+    for (entity, mut sentinel, transform) in &mut sentinel_query {
+        let forward = (transform.rotation * Vec3::Y).xy();
+        let to_player = player_translation - transform.translation.xy();
+
+        if sentinel.can_see(forward, to_player) {
+            sentinel.lose_sight_timer.reset();
+            if !sentinel.alerted {
+                sentinel.alerted = true;
+                commands.entity(entity).insert((
+                    EnemyMove {
+                        movement_speed: sentinel.movement_speed,
+                    },
+                    RotateToPlayer {
+                        rotation_speed: sentinel.rotation_speed,
+                    },
+                ));
+            }
+        } else if sentinel.alerted && sentinel.lose_sight_timer.tick(time.delta()).finished() {
+            sentinel.alerted = false;
+            sentinel.lose_sight_timer.reset();
+            commands
+                .entity(entity)
+                .remove::<(EnemyMove, RotateToPlayer)>();
+        }
+    }
+}
+
+/// Walks unalerted sentinels along their patrol route. Alerted ones chase the player instead,
+/// and pick the route back up from wherever they lost sight of it.
+fn patrol_system(
+    time: Res<Time>,
+    mut sentinel_query: Query<(&Sentinel, &mut Patrol, &mut Transform), Without<Stunned>>,
+) {
+    for (sentinel, mut patrol, mut transform) in &mut sentinel_query {
+        if sentinel.alerted {
+            continue;
+        }
+
+        let position = transform.translation.xy();
+        if position.distance(patrol.waypoints[patrol.next]) < WAYPOINT_RADIUS {
+            patrol.next = (patrol.next + 1) % patrol.waypoints.len();
+        }
+        let to_waypoint = patrol.waypoints[patrol.next] - position;
+
+        // turn toward the waypoint without overshooting it, then walk forward
+        let forward = (transform.rotation * Vec3::Y).xy();
+        let max_turn = patrol.rotation_speed * time.delta_seconds();
+        let turn = forward
+            .angle_between(to_waypoint)
+            .clamp(-max_turn, max_turn);
+        transform.rotate_z(turn);
+
+        let forward = (transform.rotation * Vec3::Y).xy();
+        let step = forward * patrol.movement_speed * time.delta_seconds();
+        transform.translation += step.extend(0.0);
+    }
+}
+
+#[cfg(feature = "dev")]
+fn draw_vision_cones(mut gizmos: Gizmos, sentinel_query: Query<(&Sentinel, &Transform)>) {
+    for (sentinel, transform) in &sentinel_query {
+        let position = transform.translation.xy();
+        let forward = (transform.rotation * Vec3::Y).xy();
+        let color = if sentinel.alerted {
+            Color::srgb(1.0, 0.3, 0.3)
+        } else {
+            Color::srgb(1.0, 1.0, 0.3)
+        };
+
+        for side in [-1.0, 1.0] {
+            let edge = Vec2::from_angle(side * sentinel.half_angle).rotate(forward);
+            gizmos.line_2d(position, position + edge * sentinel.range, color);
+        }
+        gizmos.arc_2d(
+            position,
+            Vec2::Y.angle_between(forward),
+            sentinel.half_angle * 2.0,
+            sentinel.range,
+            color,
+        );
+    }
+}
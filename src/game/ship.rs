@@ -0,0 +1,75 @@
+//! The ships the player can pick from before a run.
+
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<SelectedShip>();
+    app.init_resource::<SelectedShip>();
+}
+
+/// Everything that sets one ship apart from the others.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShipStats {
+    pub name: &'static str,
+    pub texture: &'static str,
+    pub color: Color,
+    pub scale: f32,
+    pub movement_speed: f32,
+    pub rotation_speed: f32,
+    pub max_health: f32,
+    pub laser_damage_per_second: f32,
+    pub laser_max_length: f32,
+    /// Distance from the centre of the ship to its nose, where the laser starts.
+    pub laser_muzzle_offset: f32,
+}
+
+/// Every ship the player can choose, in the order they are shown.
+pub const SHIPS: [ShipStats; 3] = [
+    ShipStats {
+        name: "Ducky",
+        texture: "images/ducky.png",
+        color: Color::WHITE,
+        scale: 0.5,
+        movement_speed: 420.0,
+        rotation_speed: 5.0,
+        max_health: 100.0,
+        laser_damage_per_second: 60.0,
+        laser_max_length: 400.0,
+        laser_muzzle_offset: 50.0,
+    },
+    ShipStats {
+        name: "Interceptor",
+        texture: "ship_C.png",
+        color: Color::srgb(0.6, 1.0, 0.7),
+        scale: 1.0,
+        movement_speed: 560.0,
+        rotation_speed: 6.5,
+        max_health: 70.0,
+        laser_damage_per_second: 45.0,
+        laser_max_length: 500.0,
+        laser_muzzle_offset: 28.0,
+    },
+    ShipStats {
+        name: "Bulwark",
+        texture: "ship_C.png",
+        color: Color::srgb(1.0, 0.75, 0.5),
+        scale: 1.3,
+        movement_speed: 320.0,
+        rotation_speed: 3.5,
+        max_health: 160.0,
+        laser_damage_per_second: 90.0,
+        laser_max_length: 300.0,
+        laser_muzzle_offset: 38.0,
+    },
+];
+
+/// Index into [`SHIPS`] of the ship the player picked.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct SelectedShip(pub usize);
+
+impl SelectedShip {
+    pub fn stats(&self) -> &'static ShipStats {
+        &SHIPS[self.0]
+    }
+}
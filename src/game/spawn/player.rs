@@ -5,7 +5,13 @@ use std::time::Duration;
 use bevy::prelude::*;
 
 use crate::{
-    game::movement::{Movement, MovementController, StepSfx, WrapWithinWindow},
+    game::{
+        combat::{Health, MaxHealth, Team},
+        movement::{Movement, MovementController, StepSfx, WrapWithinWindow},
+        rewind::PositionHistory,
+        ship::SelectedShip,
+        weapon::{AcidCharges, LaserBeam, SpinAttack, WeaponHeat},
+    },
     screen::Screen,
 };
 
@@ -25,22 +31,47 @@ fn spawn_player(
     _trigger: Trigger<SpawnPlayer>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    selected_ship: Res<SelectedShip>,
 ) {
+    let ship = selected_ship.stats();
     commands.spawn((
         Name::new("Player"),
         Player,
         SpriteBundle {
-            texture: asset_server.load("images/ducky.png"),
-            transform: Transform::from_scale(Vec3::splat(0.5)),
+            sprite: Sprite {
+                color: ship.color,
+                ..default()
+            },
+            texture: asset_server.load(ship.texture),
+            transform: Transform::from_scale(Vec3::splat(ship.scale)),
             ..Default::default()
         },
         MovementController::default(),
         Movement {
-            movement_speed: 420.0,
-            rotation_speed: 5.0,
+            movement_speed: ship.movement_speed,
+            rotation_speed: ship.rotation_speed,
         },
         WrapWithinWindow,
         StepSfx::new(Duration::from_millis(250)),
+        Health {
+            value: ship.max_health,
+        },
+        MaxHealth {
+            value: ship.max_health,
+        },
+        Team::Friendly,
+        // weapons and abilities
+        (
+            LaserBeam::new(
+                ship.laser_max_length,
+                ship.laser_damage_per_second,
+                ship.laser_muzzle_offset,
+            ),
+            WeaponHeat::default(),
+            AcidCharges::default(),
+            SpinAttack::default(),
+            PositionHistory::default(),
+        ),
         StateScoped(Screen::Playing),
     ));
 
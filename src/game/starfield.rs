@@ -0,0 +1,124 @@
+//! A starfield behind the playfield, in layers that scroll at different speeds for depth.
+
+use bevy::prelude::*;
+use rand::prelude::*;
+
+use super::spawn::player::Player;
+use crate::{
+    camera::{CameraZoom, BOUNDS},
+    screen::Screen,
+    settings::{PerformanceMode, Settings},
+    AppSet,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ParallaxLayer>();
+    app.add_systems(OnEnter(Screen::Playing), spawn_starfield);
+    app.add_systems(
+        Update,
+        (parallax_system, toggle_starfield)
+            .in_set(AppSet::Update)
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+/// `(depth, star count, star size)` of each layer, from the farthest to the nearest.
+const STAR_LAYERS: [(f32, usize, f32); 3] = [(0.05, 120, 1.5), (0.15, 60, 2.5), (0.3, 25, 3.5)];
+
+/// A layer of the starfield. It moves against the view by `depth` times the view's offset
+/// from the centre, so deeper layers use a smaller depth and barely move.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct ParallaxLayer {
+    pub depth: f32,
+}
+
+/// The starfield is hidden when turned off in the settings, and in [`PerformanceMode`].
+fn starfield_visibility(settings: &Settings, performance: PerformanceMode) -> Visibility {
+    if settings.show_background && !performance.0 {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+fn spawn_starfield(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    performance: Res<PerformanceMode>,
+) {
+    let mut rng = thread_rng();
+    // cover more than the playfield so the edges never show while a layer is offset
+    let area = BOUNDS * 1.5;
+    let visibility = starfield_visibility(&settings, *performance);
+
+    for (index, (depth, count, size)) in STAR_LAYERS.into_iter().enumerate() {
+        commands
+            .spawn((
+                Name::new("Parallax Layer"),
+                SpatialBundle {
+                    // keep the stars behind everything else, nearer layers in front
+                    transform: Transform::from_xyz(0.0, 0.0, -10.0 + index as f32),
+                    visibility,
+                    ..default()
+                },
+                ParallaxLayer { depth },
+                StateScoped(Screen::Playing),
+            ))
+            .with_children(|children| {
+                for _ in 0..count {
+                    let position = (Vec2::new(rng.gen(), rng.gen()) - 0.5) * area;
+                    children.spawn((
+                        Name::new("Star"),
+                        SpriteBundle {
+                            sprite: Sprite {
+                                color: Color::srgba(0.8, 0.85, 1.0, 0.3 + depth * 2.0),
+                                custom_size: Some(Vec2::splat(size)),
+                                ..default()
+                            },
+                            transform: Transform::from_translation(position.extend(0.0)),
+                            ..default()
+                        },
+                    ));
+                }
+            });
+    }
+}
+
+/// Offsets each layer against the view. The camera stays framed on the playfield,
+/// so the player's ship stands in for the point of view.
+fn parallax_system(
+    camera_query: Query<&Transform, With<CameraZoom>>,
+    player_query: Query<&Transform, With<Player>>,
+    mut layer_query: Query<
+        (&ParallaxLayer, &mut Transform),
+        (Without<CameraZoom>, Without<Player>),
+    >,
+) {
+    let camera_offset = camera_query
+        .get_single()
+        .map_or(Vec2::ZERO, |transform| transform.translation.xy());
+    let player_offset = player_query
+        .get_single()
+        .map_or(Vec2::ZERO, |transform| transform.translation.xy());
+    let view = camera_offset + player_offset;
+
+    for (layer, mut transform) in &mut layer_query {
+        let offset = -view * layer.depth;
+        transform.translation = offset.extend(transform.translation.z);
+    }
+}
+
+fn toggle_starfield(
+    settings: Res<Settings>,
+    performance: Res<PerformanceMode>,
+    mut layer_query: Query<&mut Visibility, With<ParallaxLayer>>,
+) {
+    if !settings.is_changed() && !performance.is_changed() {
+        return;
+    }
+
+    for mut visibility in &mut layer_query {
+        *visibility = starfield_visibility(&settings, *performance);
+    }
+}
@@ -0,0 +1,131 @@
+//! Statistics about the current run, shown on the game over screen.
+
+use bevy::{prelude::*, utils::HashMap};
+
+use super::{
+    combat::EnemyKilled,
+    enemy::{EnemyKind, RunTime},
+    portal::PortalWave,
+};
+use crate::screen::Screen;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(RunStats, BestRun)>();
+    app.init_resource::<RunStats>();
+    app.init_resource::<BestRun>();
+    app.add_systems(OnEnter(Screen::Playing), reset_run_stats);
+    app.add_systems(OnExit(Screen::Playing), record_run_end);
+    app.observe(count_kill);
+}
+
+/// What happened during the current (or last finished) run.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct RunStats {
+    /// Kills of each kind of enemy.
+    pub kills: HashMap<EnemyKind, u32>,
+    /// How long the laser was fired for, in seconds.
+    pub seconds_fired: f32,
+    /// How long the laser was hitting at least one target, in seconds.
+    pub seconds_on_target: f32,
+    /// Most kills in a row, each within [`COMBO_WINDOW`] of the previous one.
+    pub max_combo: u32,
+    /// Most volatile enemies blown up in one chain reaction.
+    pub longest_chain: u32,
+    /// Filled in when the run ends.
+    pub survival_secs: f32,
+    pub wave_reached: u32,
+    combo: u32,
+    /// Run time of the most recent kill.
+    last_kill_secs: Option<f32>,
+}
+
+/// Seconds a kill combo stays alive without another kill.
+const COMBO_WINDOW: f32 = 2.0;
+
+impl RunStats {
+    pub fn total_kills(&self) -> u32 {
+        self.kills.values().sum()
+    }
+
+    /// The combo in progress at run time `now`, or 0 if it has run out.
+    pub fn active_combo(&self, now: f32) -> u32 {
+        let alive = self
+            .last_kill_secs
+            .is_some_and(|last_kill| now - last_kill <= COMBO_WINDOW);
+        if alive {
+            self.combo
+        } else {
+            0
+        }
+    }
+
+    /// Fraction of firing time spent on target, if the laser was fired at all.
+    pub fn accuracy(&self) -> Option<f32> {
+        (self.seconds_fired > 0.0).then(|| self.seconds_on_target / self.seconds_fired)
+    }
+}
+
+/// Best results across every run this session.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct BestRun {
+    pub survival_secs: f32,
+    pub wave_reached: u32,
+    pub total_kills: u32,
+}
+
+fn reset_run_stats(mut stats: ResMut<RunStats>) {
+    *stats = RunStats::default();
+}
+
+fn record_run_end(
+    mut stats: ResMut<RunStats>,
+    run_time: Res<RunTime>,
+    portal_wave: Res<PortalWave>,
+) {
+    stats.survival_secs = run_time.elapsed_secs();
+    stats.wave_reached = portal_wave.0;
+}
+
+fn count_kill(
+    trigger: Trigger<EnemyKilled>,
+    run_time: Res<RunTime>,
+    mut stats: ResMut<RunStats>,
+    kind_query: Query<&EnemyKind>,
+) {
+    if let Ok(kind) = kind_query.get(trigger.entity()) {
+        *stats.kills.entry(*kind).or_default() += 1;
+    }
+
+    let now = run_time.elapsed_secs();
+    let in_combo = stats
+        .last_kill_secs
+        .is_some_and(|last_kill| now - last_kill <= COMBO_WINDOW);
+    stats.combo = if in_combo { stats.combo + 1 } else { 1 };
+    stats.max_combo = stats.max_combo.max(stats.combo);
+    stats.last_kill_secs = Some(now);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kills_are_counted_by_enemy_kind() {
+        let mut app = App::new();
+        app.init_resource::<RunTime>();
+        app.init_resource::<RunStats>();
+        app.observe(count_kill);
+
+        for kind in [EnemyKind::Drone, EnemyKind::Snapper, EnemyKind::Drone] {
+            let enemy = app.world_mut().spawn(kind).id();
+            app.world_mut().trigger_targets(EnemyKilled, enemy);
+        }
+
+        let stats = app.world().resource::<RunStats>();
+        assert_eq!(stats.kills[&EnemyKind::Drone], 2);
+        assert_eq!(stats.kills[&EnemyKind::Snapper], 1);
+        assert_eq!(stats.total_kills(), 3);
+    }
+}
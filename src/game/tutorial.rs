@@ -0,0 +1,142 @@
+//! A short tutorial for first-time players, shown at the start of a run until it is finished once.
+
+use bevy::prelude::*;
+
+use super::{
+    hud::{HudAnchor, HudElement},
+    input::{Action, ActionState},
+    movement::MovementController,
+    single_or_skip,
+    spawn::player::Player,
+    weapon::LaserBeam,
+};
+use crate::{screen::Screen, settings::Settings, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(TutorialStep, TutorialText)>();
+    app.init_resource::<TutorialStep>();
+    app.add_systems(OnEnter(Screen::Playing), start_tutorial);
+    app.add_systems(
+        Update,
+        (advance_tutorial, skip_tutorial, update_tutorial_text)
+            .chain()
+            .in_set(AppSet::Update)
+            .run_if(in_state(Screen::Playing).and_then(any_with_component::<TutorialText>)),
+    );
+}
+
+/// The action the tutorial is currently waiting for.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub enum TutorialStep {
+    #[default]
+    Move,
+    Turn,
+    Shoot,
+    Done,
+}
+
+impl TutorialStep {
+    fn prompt(self) -> &'static str {
+        match self {
+            Self::Move => "W to move forward",
+            Self::Turn => "A / D to turn",
+            Self::Shoot => "Space to fire the laser",
+            Self::Done => "",
+        }
+    }
+}
+
+/// The prompt for the current [`TutorialStep`].
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct TutorialText;
+
+fn start_tutorial(mut commands: Commands, settings: Res<Settings>, mut step: ResMut<TutorialStep>) {
+    if !settings.show_tutorial {
+        return;
+    }
+
+    *step = TutorialStep::default();
+    commands.spawn((
+        Name::new("Tutorial Text"),
+        TextBundle::from_sections([
+            TextSection::new(
+                step.prompt(),
+                TextStyle {
+                    font_size: 40.0,
+                    ..default()
+                },
+            ),
+            TextSection::new(
+                "\nEnter to skip",
+                TextStyle {
+                    font_size: 20.0,
+                    color: Color::srgb(0.6, 0.6, 0.6),
+                    ..default()
+                },
+            ),
+        ])
+        .with_text_justify(JustifyText::Center)
+        .with_style(Style {
+            margin: UiRect::top(Val::Px(60.0)),
+            ..default()
+        }),
+        HudElement(HudAnchor::TopCenter),
+        TutorialText,
+        StateScoped(Screen::Playing),
+    ));
+}
+
+/// Moves on to the next step once the player performs the current one.
+fn advance_tutorial(
+    mut step: ResMut<TutorialStep>,
+    player_query: Query<(&MovementController, &LaserBeam), With<Player>>,
+) {
+    let Some((controller, laser)) = single_or_skip(&player_query) else {
+        return;
+    };
+
+    let performed = match *step {
+        TutorialStep::Move => controller.movement_intent() != 0.0,
+        TutorialStep::Turn => controller.rotation_intent() != 0.0,
+        TutorialStep::Shoot => laser.is_firing(),
+        TutorialStep::Done => false,
+    };
+    if performed {
+        *step = match *step {
+            TutorialStep::Move => TutorialStep::Turn,
+            TutorialStep::Turn => TutorialStep::Shoot,
+            TutorialStep::Shoot | TutorialStep::Done => TutorialStep::Done,
+        };
+    }
+}
+
+fn skip_tutorial(actions: Res<ActionState>, mut step: ResMut<TutorialStep>) {
+    if actions.just_pressed(Action::Skip) {
+        *step = TutorialStep::Done;
+    }
+}
+
+/// Shows the current prompt, and turns the tutorial off for future runs once it is done.
+fn update_tutorial_text(
+    mut commands: Commands,
+    step: Res<TutorialStep>,
+    mut settings: ResMut<Settings>,
+    mut text_query: Query<(Entity, &mut Text), With<TutorialText>>,
+) {
+    if !step.is_changed() {
+        return;
+    }
+
+    for (entity, mut text) in &mut text_query {
+        if *step == TutorialStep::Done {
+            commands.entity(entity).despawn_recursive();
+        } else {
+            text.sections[0].value = step.prompt().to_string();
+        }
+    }
+    if *step == TutorialStep::Done {
+        settings.show_tutorial = false;
+    }
+}
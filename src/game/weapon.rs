@@ -0,0 +1,550 @@
+//! Player weapons: the laser, deployable acid pools and the spin attack.
+
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle, ui::Val::*};
+
+use super::{
+    combat::{
+        apply_damage, distance_to_segment, DamageType, FriendlyFire, Health, Knockback,
+        Resistances, Team,
+    },
+    enemy::Stunned,
+    hud::{HudAnchor, HudElement},
+    input::{Action, ActionState},
+    lifetime::Lifetime,
+    protector::Protected,
+    single_or_skip,
+    spawn::player::Player,
+    stats::RunStats,
+};
+use crate::{screen::Screen, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    // Fire the laser while the fire key is held.
+    app.register_type::<LaserBeam>();
+    app.add_systems(
+        Update,
+        (
+            record_laser_input.in_set(AppSet::RecordInput),
+            (apply_laser_damage, draw_laser_beam).in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+
+    // Heat the laser up while it fires and block it while overheated.
+    app.register_type::<(WeaponHeat, HeatGaugeFill)>();
+    app.add_systems(OnEnter(Screen::Playing), spawn_heat_gauge);
+    app.add_systems(
+        Update,
+        (update_weapon_heat, update_heat_gauge)
+            .chain()
+            .before(apply_laser_damage)
+            .in_set(AppSet::Update)
+            .run_if(in_state(Screen::Playing)),
+    );
+
+    // Drop acid pools that hurt enemies standing in them.
+    app.register_type::<(AcidCharges, AcidPool)>();
+    app.add_systems(
+        Update,
+        (deploy_acid_pool, acid_pool_system, fade_acid_pools)
+            .in_set(AppSet::Update)
+            .run_if(in_state(Screen::Playing)),
+    );
+
+    // Spin to hit and shove away everything close around the ship.
+    app.register_type::<(SpinAttack, SpinSlash)>();
+    app.add_systems(
+        Update,
+        (
+            tick_spin_attacks.in_set(AppSet::TickTimers),
+            (spin_attack_system, spin_slashes).in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+/// A continuous beam fired forward from the ship while the fire key is held.
+/// The firing ship is never hit by its own beam.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct LaserBeam {
+    /// How far the beam reaches from the muzzle.
+    pub max_length: f32,
+    /// How far ahead of the ship's centre the beam starts, so it doesn't draw over the ship.
+    pub muzzle_offset: f32,
+    /// Enemies closer than this to the beam are hit.
+    pub half_width: f32,
+    /// Damage dealt to each enemy on the beam, per second.
+    pub damage_per_second: f32,
+    pub damage_type: DamageType,
+    /// Enemies on the beam when it starts firing are [`Stunned`] for this long.
+    pub stun_duration: f32,
+    firing: bool,
+    /// Whether the beam started firing this frame.
+    burst_started: bool,
+}
+
+impl Default for LaserBeam {
+    fn default() -> Self {
+        Self {
+            max_length: 400.0,
+            muzzle_offset: 0.0,
+            half_width: 20.0,
+            damage_per_second: 60.0,
+            damage_type: DamageType::Energy,
+            stun_duration: 0.5,
+            firing: false,
+            burst_started: false,
+        }
+    }
+}
+
+impl LaserBeam {
+    pub fn new(max_length: f32, damage_per_second: f32, muzzle_offset: f32) -> Self {
+        Self {
+            max_length,
+            damage_per_second,
+            muzzle_offset,
+            ..default()
+        }
+    }
+
+    pub fn is_firing(&self) -> bool {
+        self.firing
+    }
+
+    /// Start and end points of the beam fired from `transform`.
+    fn segment(&self, transform: &Transform) -> (Vec2, Vec2) {
+        let forward = (transform.rotation * Vec3::Y).xy();
+        let start = transform.translation.xy() + forward * self.muzzle_offset;
+        (start, start + forward * self.max_length)
+    }
+}
+
+/// Heat built up by firing. Once `heat` reaches `max` the weapon is overheated and can't fire
+/// until it has cooled all the way down.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct WeaponHeat {
+    pub heat: f32,
+    pub max: f32,
+    /// Heat added per second of firing.
+    pub heat_rate: f32,
+    /// Heat removed per second while not firing.
+    pub cooldown_rate: f32,
+    overheated: bool,
+}
+
+impl Default for WeaponHeat {
+    fn default() -> Self {
+        Self {
+            heat: 0.0,
+            max: 100.0,
+            heat_rate: 30.0,
+            cooldown_rate: 40.0,
+            overheated: false,
+        }
+    }
+}
+
+/// How many acid pools the ship can still drop.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct AcidCharges {
+    pub remaining: u32,
+}
+
+impl Default for AcidCharges {
+    fn default() -> Self {
+        Self { remaining: 3 }
+    }
+}
+
+/// A close-range attack that damages and knocks back every opposing entity within
+/// `radius` of the ship. It can be used again once `cooldown` finishes.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct SpinAttack {
+    pub cooldown: Timer,
+    pub radius: f32,
+    pub damage: f32,
+    /// Speed the hit entities are knocked back at.
+    pub knockback: f32,
+}
+
+impl Default for SpinAttack {
+    fn default() -> Self {
+        let mut cooldown = Timer::from_seconds(3.0, TimerMode::Once);
+        // ready from the start of the run
+        cooldown.tick(cooldown.duration());
+        Self {
+            cooldown,
+            radius: 110.0,
+            damage: 30.0,
+            knockback: 900.0,
+        }
+    }
+}
+
+/// The blade sweeping around the ship during a spin attack. Purely visual.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct SpinSlash;
+
+const SPIN_SLASH_COLOR: Color = Color::srgba(0.9, 0.95, 1.0, 0.8);
+const SPIN_SLASH_DURATION: f32 = 0.3;
+/// Radians the slash turns per second, so it goes round about twice.
+const SPIN_SLASH_SPEED: f32 = 4.0 * std::f32::consts::TAU;
+
+/// A pool of acid left on the ground. Opposing entities within `radius` of it take
+/// `damage_per_second` corrosive damage. Despawned by its [`Lifetime`].
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct AcidPool {
+    pub radius: f32,
+    pub damage_per_second: f32,
+}
+
+const ACID_COLOR: Color = Color::srgb(0.45, 1.0, 0.2);
+/// Opacity of a fresh acid pool, faded out over its lifetime.
+const ACID_ALPHA: f32 = 0.35;
+const ACID_POOL_DURATION: f32 = 6.0;
+
+/// The fill bar of the heat gauge in the corner of the screen.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct HeatGaugeFill;
+
+const HEAT_GAUGE_COLOR: Color = Color::srgb(1.0, 0.55, 0.1);
+const OVERHEATED_COLOR: Color = Color::srgb(1.0, 0.15, 0.15);
+
+fn record_laser_input(
+    actions: Res<ActionState>,
+    mut laser_query: Query<&mut LaserBeam, With<Player>>,
+) {
+    let firing = actions.pressed(Action::Fire);
+    for mut laser in &mut laser_query {
+        laser.burst_started = firing && !laser.firing;
+        laser.firing = firing;
+    }
+}
+
+/// Damages every opposing entity within the beam's width of the beam segment,
+/// and stuns the ones hit by the start of a burst.
+fn apply_laser_damage(
+    mut commands: Commands,
+    time: Res<Time>,
+    friendly_fire: Res<FriendlyFire>,
+    mut stats: ResMut<RunStats>,
+    laser_query: Query<(&LaserBeam, &Transform, &Team, Has<Player>)>,
+    mut target_query: Query<
+        (
+            Entity,
+            &mut Health,
+            &Transform,
+            &Team,
+            Option<&Resistances>,
+            Option<&Protected>,
+        ),
+        Without<LaserBeam>,
+    >,
+) {
+    for (laser, laser_transform, laser_team, is_player) in &laser_query {
+        if !laser.firing {
+            continue;
+        }
+
+        let mut on_target = false;
+        let (start, end) = laser.segment(laser_transform);
+        for (target, mut health, target_transform, target_team, resistances, protected) in
+            &mut target_query
+        {
+            if !target_team.is_hurt_by(*laser_team, friendly_fire.0) {
+                continue;
+            }
+
+            let distance = distance_to_segment(target_transform.translation.xy(), start, end);
+            if distance < laser.half_width {
+                let damage = laser.damage_per_second * time.delta_seconds();
+                let amount = Protected::apply(
+                    protected,
+                    Resistances::apply(resistances, laser.damage_type, damage),
+                );
+                apply_damage(&mut commands, target, &mut health, amount);
+                on_target = true;
+
+                if laser.burst_started && laser.stun_duration > 0.0 {
+                    commands.entity(target).insert(Stunned {
+                        timer: Timer::from_seconds(laser.stun_duration, TimerMode::Once),
+                    });
+                }
+            }
+        }
+
+        if is_player {
+            stats.seconds_fired += time.delta_seconds();
+            if on_target {
+                stats.seconds_on_target += time.delta_seconds();
+            }
+        }
+    }
+}
+
+/// Builds up heat while the laser fires and cools it down otherwise.
+/// An overheated laser stops firing until its heat is back to zero.
+fn update_weapon_heat(time: Res<Time>, mut laser_query: Query<(&mut LaserBeam, &mut WeaponHeat)>) {
+    for (mut laser, mut heat) in &mut laser_query {
+        if heat.overheated {
+            laser.firing = false;
+        }
+
+        if laser.firing {
+            heat.heat = (heat.heat + heat.heat_rate * time.delta_seconds()).min(heat.max);
+            heat.overheated = heat.heat >= heat.max;
+        } else {
+            heat.heat = (heat.heat - heat.cooldown_rate * time.delta_seconds()).max(0.0);
+            heat.overheated &= heat.heat > 0.0;
+        }
+    }
+}
+
+fn spawn_heat_gauge(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("Heat Gauge"),
+            NodeBundle {
+                style: Style {
+                    width: Px(200.0),
+                    height: Px(14.0),
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+                ..default()
+            },
+            HudElement(HudAnchor::BottomLeft),
+            StateScoped(Screen::Playing),
+        ))
+        .with_children(|children| {
+            children.spawn((
+                Name::new("Heat Gauge Fill"),
+                NodeBundle {
+                    style: Style {
+                        width: Percent(0.0),
+                        height: Percent(100.0),
+                        ..default()
+                    },
+                    background_color: BackgroundColor(HEAT_GAUGE_COLOR),
+                    ..default()
+                },
+                HeatGaugeFill,
+            ));
+        });
+}
+
+/// Fills the heat gauge with the player's weapon heat, flashing it while overheated.
+fn update_heat_gauge(
+    time: Res<Time>,
+    heat_query: Query<&WeaponHeat, With<Player>>,
+    mut fill_query: Query<(&mut Style, &mut BackgroundColor), With<HeatGaugeFill>>,
+) {
+    let Some(heat) = single_or_skip(&heat_query) else {
+        return;
+    };
+
+    for (mut style, mut background) in &mut fill_query {
+        style.width = Percent(100.0 * heat.heat / heat.max);
+
+        let flash_on = (time.elapsed_seconds() * 8.0).sin() > 0.0;
+        background.0 = if heat.overheated && flash_on {
+            OVERHEATED_COLOR
+        } else {
+            HEAT_GAUGE_COLOR
+        };
+    }
+}
+
+fn draw_laser_beam(mut gizmos: Gizmos, laser_query: Query<(&LaserBeam, &Transform)>) {
+    for (laser, transform) in &laser_query {
+        if laser.firing {
+            let (start, end) = laser.segment(transform);
+            gizmos.line_2d(start, end, Color::srgb(1.0, 0.2, 0.2));
+        }
+    }
+}
+
+/// Drops an acid pool under the ship when the deploy key is pressed, using up a charge.
+fn deploy_acid_pool(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut player_query: Query<(&mut AcidCharges, &Transform, &Team), With<Player>>,
+) {
+    if !actions.just_pressed(Action::DeployAcid) {
+        return;
+    }
+
+    for (mut charges, transform, team) in &mut player_query {
+        if charges.remaining == 0 {
+            continue;
+        }
+        charges.remaining -= 1;
+
+        let pool = AcidPool {
+            radius: 80.0,
+            damage_per_second: 25.0,
+        };
+        commands.spawn((
+            Name::new("Acid Pool"),
+            MaterialMesh2dBundle {
+                mesh: meshes.add(Circle::new(pool.radius)).into(),
+                material: materials.add(ACID_COLOR.with_alpha(ACID_ALPHA)),
+                // under the ships and enemies
+                transform: Transform::from_translation(transform.translation.xy().extend(-1.0)),
+                ..default()
+            },
+            pool,
+            *team,
+            Lifetime::from_seconds(ACID_POOL_DURATION),
+            StateScoped(Screen::Playing),
+        ));
+    }
+}
+
+/// Damages every opposing entity inside an acid pool.
+fn acid_pool_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    friendly_fire: Res<FriendlyFire>,
+    pool_query: Query<(&AcidPool, &Transform, &Team)>,
+    mut target_query: Query<
+        (
+            Entity,
+            &mut Health,
+            &Transform,
+            &Team,
+            Option<&Resistances>,
+            Option<&Protected>,
+        ),
+        Without<AcidPool>,
+    >,
+) {
+    for (pool, pool_transform, pool_team) in &pool_query {
+        let center = pool_transform.translation.xy();
+        for (target, mut health, target_transform, target_team, resistances, protected) in
+            &mut target_query
+        {
+            if !target_team.is_hurt_by(*pool_team, friendly_fire.0) {
+                continue;
+            }
+
+            if target_transform.translation.xy().distance(center) < pool.radius {
+                let damage = pool.damage_per_second * time.delta_seconds();
+                let amount = Protected::apply(
+                    protected,
+                    Resistances::apply(resistances, DamageType::Corrosive, damage),
+                );
+                apply_damage(&mut commands, target, &mut health, amount);
+            }
+        }
+    }
+}
+
+fn fade_acid_pools(
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    pool_query: Query<(&Lifetime, &Handle<ColorMaterial>), With<AcidPool>>,
+) {
+    for (lifetime, material) in &pool_query {
+        if let Some(material) = materials.get_mut(material) {
+            material.color = ACID_COLOR.with_alpha(ACID_ALPHA * (1.0 - lifetime.fraction()));
+        }
+    }
+}
+
+fn tick_spin_attacks(time: Res<Time>, mut spin_query: Query<&mut SpinAttack>) {
+    for mut spin in &mut spin_query {
+        spin.cooldown.tick(time.delta());
+    }
+}
+
+fn spin_attack_system(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    friendly_fire: Res<FriendlyFire>,
+    mut player_query: Query<(Entity, &mut SpinAttack, &Transform, &Team), With<Player>>,
+    mut target_query: Query<
+        (
+            Entity,
+            &mut Health,
+            &Transform,
+            &Team,
+            Option<&Resistances>,
+            Option<&Protected>,
+        ),
+        Without<Player>,
+    >,
+) {
+    if !actions.just_pressed(Action::SpinAttack) {
+        return;
+    }
+
+    for (player, mut spin, transform, team) in &mut player_query {
+        if !spin.cooldown.finished() {
+            continue;
+        }
+        spin.cooldown.reset();
+
+        let center = transform.translation.xy();
+        for (target, mut health, target_transform, target_team, resistances, protected) in
+            &mut target_query
+        {
+            if !target_team.is_hurt_by(*team, friendly_fire.0) {
+                continue;
+            }
+
+            let offset = target_transform.translation.xy() - center;
+            if offset.length() < spin.radius {
+                let amount = Protected::apply(
+                    protected,
+                    Resistances::apply(resistances, DamageType::Kinetic, spin.damage),
+                );
+                apply_damage(&mut commands, target, &mut health, amount);
+                let direction = offset.normalize_or(Vec2::Y);
+                commands
+                    .entity(target)
+                    .insert(Knockback::new(direction * spin.knockback, 0.3));
+            }
+        }
+
+        // a blade reaching out to the edge of the attack, spun around the ship, sized to
+        // undo the scale of the ship it is attached to
+        let size = Vec2::new(spin.radius * 2.0, 6.0) / transform.scale.xy();
+        commands.entity(player).with_children(|children| {
+            children.spawn((
+                Name::new("Spin Slash"),
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: SPIN_SLASH_COLOR,
+                        custom_size: Some(size),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, 0.0, 1.0),
+                    ..default()
+                },
+                SpinSlash,
+                Lifetime::from_seconds(SPIN_SLASH_DURATION),
+            ));
+        });
+    }
+}
+
+/// Turns spin slashes around the ship and fades them out.
+fn spin_slashes(
+    time: Res<Time>,
+    mut slash_query: Query<(&Lifetime, &mut Transform, &mut Sprite), With<SpinSlash>>,
+) {
+    for (lifetime, mut transform, mut sprite) in &mut slash_query {
+        transform.rotate_z(SPIN_SLASH_SPEED * time.delta_seconds());
+        sprite.color = SPIN_SLASH_COLOR.with_alpha(0.8 * (1.0 - lifetime.fraction()));
+    }
+}
@@ -0,0 +1,328 @@
+//! Experience orbs dropped by kills, and a pick-one upgrade for the ship every time it
+//! levels up.
+//! Nothing here outlives the run.
+
+use bevy::prelude::*;
+
+use super::{
+    combat::{EnemyKilled, Health, MaxHealth},
+    enemy::Enemy,
+    hud::{HudAnchor, HudElement},
+    lifetime::Lifetime,
+    movement::Movement,
+    single_or_skip, single_or_skip_mut,
+    spawn::player::Player,
+    weapon::{LaserBeam, WeaponHeat},
+};
+use crate::{screen::Screen, ui_tools::prelude::*, AppSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_sub_state::<UpgradeMenu>();
+    app.enable_state_scoped_entities::<UpgradeMenu>();
+    app.register_type::<(PlayerXp, XpReward, XpOrb, UpgradeChoice, XpLabel)>();
+    app.init_resource::<PlayerXp>();
+    app.add_systems(OnEnter(Screen::Playing), (reset_player_xp, spawn_xp_label));
+    app.observe(drop_xp_orb);
+    app.add_systems(
+        Update,
+        (
+            assign_xp_rewards,
+            collect_xp_orbs,
+            open_upgrade_menu,
+            update_xp_label,
+        )
+            .in_set(AppSet::Update)
+            .run_if(in_state(Screen::Playing)),
+    );
+
+    // Every `AppSet` is paused while the menu is open, so its systems stay out of them.
+    app.add_systems(OnEnter(UpgradeMenu::Open), (pause_time, spawn_upgrade_menu));
+    app.add_systems(OnExit(UpgradeMenu::Open), resume_time);
+    app.add_systems(
+        Update,
+        handle_upgrade_choice.run_if(in_state(UpgradeMenu::Open)),
+    );
+}
+
+/// Experience needed for the first level-up.
+const FIRST_LEVEL_XP: u32 = 10;
+/// How much more experience each level needs than the one before.
+const XP_GROWTH: f32 = 1.5;
+/// Experience per point of an enemy's starting health.
+const XP_PER_HEALTH: f32 = 0.1;
+const XP_ORB_COLOR: Color = Color::srgb(0.5, 1.0, 0.3);
+/// Orbs left uncollected this long fade away.
+const XP_ORB_LIFETIME: f32 = 20.0;
+/// Orbs within this distance of the player fly toward it.
+const XP_ORB_MAGNET_RADIUS: f32 = 120.0;
+const XP_ORB_MAGNET_SPEED: f32 = 400.0;
+/// Orbs within this distance of the player are collected.
+const XP_ORB_PICKUP_RADIUS: f32 = 24.0;
+
+/// The player's experience this run.
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct PlayerXp {
+    /// Experience toward the next level.
+    pub current: u32,
+    pub to_next: u32,
+    pub level: u32,
+    /// Level-ups whose upgrade hasn't been picked yet.
+    pub pending_upgrades: u32,
+}
+
+impl Default for PlayerXp {
+    fn default() -> Self {
+        Self {
+            current: 0,
+            to_next: FIRST_LEVEL_XP,
+            level: 1,
+            pending_upgrades: 0,
+        }
+    }
+}
+
+impl PlayerXp {
+    /// Adds `amount` experience, levelling up as many times as it covers.
+    pub fn gain(&mut self, amount: u32) {
+        self.current += amount;
+        while self.current >= self.to_next {
+            self.current -= self.to_next;
+            self.level += 1;
+            self.pending_upgrades += 1;
+            self.to_next = (self.to_next as f32 * XP_GROWTH).ceil() as u32;
+        }
+    }
+}
+
+/// Experience the player gains for killing this enemy.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct XpReward(pub u32);
+
+/// Experience dropped where an enemy died, gained once the player collects it.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct XpOrb {
+    pub amount: u32,
+}
+
+/// Whether the level-up menu is open. Only exists while playing.
+#[derive(SubStates, Debug, Hash, PartialEq, Eq, Clone, Copy, Default)]
+#[source(Screen = Screen::Playing)]
+pub enum UpgradeMenu {
+    #[default]
+    Closed,
+    Open,
+}
+
+/// An improvement to the ship offered on level-up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum Upgrade {
+    MaxHealth,
+    FireRate,
+    Damage,
+    Speed,
+}
+
+impl Upgrade {
+    const ALL: [Self; 4] = [Self::MaxHealth, Self::FireRate, Self::Damage, Self::Speed];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::MaxHealth => "+25 max health",
+            Self::FireRate => "Laser heats 20% slower",
+            Self::Damage => "+20% laser damage",
+            Self::Speed => "+10% movement speed",
+        }
+    }
+}
+
+/// A button in the level-up menu.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+struct UpgradeChoice(Upgrade);
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct XpLabel;
+
+fn reset_player_xp(mut xp: ResMut<PlayerXp>) {
+    *xp = PlayerXp::default();
+}
+
+/// Tougher enemies are worth more, based on the health they spawn with.
+fn assign_xp_rewards(
+    mut commands: Commands,
+    enemy_query: Query<(Entity, &Health), (Added<Enemy>, Without<XpReward>)>,
+) {
+    for (enemy, health) in &enemy_query {
+        let reward = ((health.value * XP_PER_HEALTH).ceil() as u32).max(1);
+        commands.entity(enemy).insert(XpReward(reward));
+    }
+}
+
+fn drop_xp_orb(
+    trigger: Trigger<EnemyKilled>,
+    mut commands: Commands,
+    reward_query: Query<(&XpReward, &Transform)>,
+) {
+    let Ok((reward, transform)) = reward_query.get(trigger.entity()) else {
+        return;
+    };
+
+    // bigger rewards make bigger orbs
+    let size = 6.0 + (reward.0 as f32).sqrt() * 2.0;
+    commands.spawn((
+        Name::new("XP Orb"),
+        SpriteBundle {
+            sprite: Sprite {
+                color: XP_ORB_COLOR,
+                custom_size: Some(Vec2::splat(size)),
+                ..default()
+            },
+            transform: Transform::from_translation(transform.translation.xy().extend(0.0))
+                .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4)),
+            ..default()
+        },
+        XpOrb { amount: reward.0 },
+        Lifetime::from_seconds(XP_ORB_LIFETIME),
+        StateScoped(Screen::Playing),
+    ));
+}
+
+/// Pulls nearby orbs toward the player, and gains the experience of those it touches.
+fn collect_xp_orbs(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut xp: ResMut<PlayerXp>,
+    player_query: Query<&Transform, With<Player>>,
+    mut orb_query: Query<(Entity, &XpOrb, &mut Transform), Without<Player>>,
+) {
+    let Some(player_transform) = single_or_skip(&player_query) else {
+        return;
+    };
+    let target = player_transform.translation.xy();
+
+    for (orb, XpOrb { amount }, mut transform) in &mut orb_query {
+        let position = transform.translation.xy();
+        let distance = position.distance(target);
+        if distance < XP_ORB_PICKUP_RADIUS {
+            xp.gain(*amount);
+            commands.entity(orb).despawn();
+        } else if distance < XP_ORB_MAGNET_RADIUS {
+            let moved = position.move_towards(target, XP_ORB_MAGNET_SPEED * time.delta_seconds());
+            transform.translation = moved.extend(transform.translation.z);
+        }
+    }
+}
+
+/// Opens the menu for the next pending level-up, one at a time.
+fn open_upgrade_menu(xp: Res<PlayerXp>, mut next_menu: ResMut<NextState<UpgradeMenu>>) {
+    if xp.pending_upgrades > 0 {
+        next_menu.set(UpgradeMenu::Open);
+    }
+}
+
+fn pause_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+fn resume_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+fn spawn_upgrade_menu(mut commands: Commands, xp: Res<PlayerXp>) {
+    commands
+        .ui_root()
+        .insert((
+            Name::new("Upgrade Menu"),
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            StateScoped(UpgradeMenu::Open),
+        ))
+        .with_children(|children| {
+            children.label(format!("Level {}!", xp.level));
+            for (index, upgrade) in Upgrade::ALL.into_iter().enumerate() {
+                children
+                    .button(upgrade.label())
+                    .insert((UpgradeChoice(upgrade), MenuButton { index }))
+                    .insert(Style {
+                        width: Val::Px(450.0),
+                        height: Val::Px(65.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    });
+            }
+        });
+}
+
+fn handle_upgrade_choice(
+    mut xp: ResMut<PlayerXp>,
+    mut next_menu: ResMut<NextState<UpgradeMenu>>,
+    mut button_query: InteractionQuery<&UpgradeChoice>,
+    mut player_query: Query<
+        (
+            &mut Health,
+            &mut MaxHealth,
+            &mut LaserBeam,
+            &mut WeaponHeat,
+            &mut Movement,
+        ),
+        With<Player>,
+    >,
+) {
+    let Some(UpgradeChoice(upgrade)) = button_query
+        .iter_mut()
+        .find(|(interaction, _)| matches!(interaction, Interaction::Pressed))
+        .map(|(_, choice)| *choice)
+    else {
+        return;
+    };
+    let Some((mut health, mut max_health, mut laser, mut heat, mut movement)) =
+        single_or_skip_mut(&mut player_query)
+    else {
+        return;
+    };
+
+    match upgrade {
+        Upgrade::MaxHealth => {
+            // the new health comes already repaired
+            max_health.value += 25.0;
+            health.value += 25.0;
+        }
+        Upgrade::FireRate => heat.heat_rate *= 0.8,
+        Upgrade::Damage => laser.damage_per_second *= 1.2,
+        Upgrade::Speed => movement.movement_speed *= 1.1,
+    }
+    info!("Picked upgrade: {}", upgrade.label());
+
+    xp.pending_upgrades = xp.pending_upgrades.saturating_sub(1);
+    next_menu.set(UpgradeMenu::Closed);
+}
+
+fn spawn_xp_label(mut commands: Commands) {
+    commands.spawn((
+        Name::new("XP Label"),
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 24.0,
+                ..default()
+            },
+        ),
+        XpLabel,
+        HudElement(HudAnchor::BottomCenter),
+        StateScoped(Screen::Playing),
+    ));
+}
+
+fn update_xp_label(xp: Res<PlayerXp>, mut label_query: Query<&mut Text, With<XpLabel>>) {
+    if !xp.is_changed() {
+        return;
+    }
+
+    for mut text in &mut label_query {
+        text.sections[0].value = format!("Level {}  {}/{} XP", xp.level, xp.current, xp.to_next);
+    }
+}
@@ -2,12 +2,15 @@ mod camera;
 #[cfg(feature = "dev")]
 mod dev_tools;
 mod game;
+mod photo_mode;
 mod screen;
+mod settings;
 mod ui_tools;
 
 use bevy::{
     asset::AssetMetaCheck,
     audio::{AudioPlugin, Volume},
+    ecs::schedule::ScheduleLabel,
     prelude::*,
 };
 
@@ -15,11 +18,18 @@ pub struct AppPlugin;
 
 impl Plugin for AppPlugin {
     fn build(&self, app: &mut App) {
-        // Order new `AppStep` variants by adding them here:
-        app.configure_sets(
-            Update,
-            (AppSet::TickTimers, AppSet::RecordInput, AppSet::Update).chain(),
-        );
+        // Order new `AppStep` variants by adding them here.
+        // `FixedUpdate` uses the same sets for systems stepped by a fixed `TimestepMode`.
+        for schedule in [Update.intern(), FixedUpdate.intern()] {
+            app.configure_sets(
+                schedule,
+                (AppSet::TickTimers, AppSet::RecordInput, AppSet::Update)
+                    .chain()
+                    .run_if(not(in_state(photo_mode::PhotoMode::On)))
+                    .run_if(not(in_state(game::xp::UpgradeMenu::Open)))
+                    .run_if(not(in_state(game::intro::Intro::Running))),
+            );
+        }
 
         // Add Bevy plugins.
         app.add_plugins(
@@ -56,6 +66,8 @@ impl Plugin for AppPlugin {
             screen::plugin,
             ui_tools::plugin,
             camera::plugin,
+            settings::plugin,
+            photo_mode::plugin,
         ));
 
         // Enable dev tools for dev builds.
@@ -64,7 +76,8 @@ impl Plugin for AppPlugin {
     }
 }
 
-/// High-level groupings of systems for the app in the `Update` schedule.
+/// High-level groupings of systems for the app in the `Update` schedule, and in
+/// `FixedUpdate` for the systems stepped there.
 /// When adding a new variant, make sure to order it in the `configure_sets`
 /// call above.
 #[derive(SystemSet, Debug, Clone, Copy, Eq, PartialEq, Hash)]
@@ -0,0 +1,154 @@
+//! Photo mode: pauses the game, hides the UI and frees the camera for screenshots.
+
+use bevy::{
+    input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
+    prelude::*,
+};
+
+use crate::{
+    camera::{window_to_world, CameraZoom},
+    game::{
+        input::{action_just_pressed, Action, ActionState},
+        xp::UpgradeMenu,
+    },
+    screen::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_sub_state::<PhotoMode>();
+    app.register_type::<HiddenForPhoto>();
+    app.add_systems(
+        Update,
+        // the upgrade menu has already paused time, and resuming it is up to the menu
+        toggle_photo_mode
+            .run_if(
+                in_state(Screen::Playing).and_then(action_just_pressed(Action::TogglePhotoMode)),
+            )
+            .run_if(not(in_state(UpgradeMenu::Open))),
+    );
+    app.add_systems(OnEnter(PhotoMode::On), (pause_time, hide_ui));
+    app.add_systems(OnExit(PhotoMode::On), (resume_time, show_ui));
+    app.add_systems(Update, free_camera_system.run_if(in_state(PhotoMode::On)));
+}
+
+/// Whether photo mode is on. Only exists while playing, so leaving the game also leaves
+/// photo mode. Every [`AppSet`](crate::AppSet) is paused while it is on.
+#[derive(SubStates, Debug, Hash, PartialEq, Eq, Clone, Copy, Default)]
+#[source(Screen = Screen::Playing)]
+pub enum PhotoMode {
+    #[default]
+    Off,
+    On,
+}
+
+/// A top-level UI node hidden for photo mode, with the visibility to restore afterwards.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct HiddenForPhoto(Visibility);
+
+/// Pan speed of the free camera in world units per second, at a projection scale of 1.
+const PAN_SPEED: f32 = 500.0;
+/// Fraction of the projection scale removed per line scrolled.
+const ZOOM_STEP: f32 = 0.1;
+const MIN_ZOOM: f32 = 0.1;
+const MAX_ZOOM: f32 = 4.0;
+
+fn toggle_photo_mode(mode: Res<State<PhotoMode>>, mut next_mode: ResMut<NextState<PhotoMode>>) {
+    next_mode.set(match mode.get() {
+        PhotoMode::Off => PhotoMode::On,
+        PhotoMode::On => PhotoMode::Off,
+    });
+}
+
+/// Freezes anything reading the virtual clock, so the run time doesn't advance either.
+fn pause_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+fn resume_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+fn hide_ui(
+    mut commands: Commands,
+    mut node_query: Query<(Entity, &mut Visibility), (With<Node>, Without<Parent>)>,
+) {
+    for (entity, mut visibility) in &mut node_query {
+        commands.entity(entity).insert(HiddenForPhoto(*visibility));
+        *visibility = Visibility::Hidden;
+    }
+}
+
+fn show_ui(
+    mut commands: Commands,
+    mut node_query: Query<(Entity, &HiddenForPhoto, &mut Visibility)>,
+) {
+    for (entity, hidden, mut visibility) in &mut node_query {
+        *visibility = hidden.0;
+        commands.entity(entity).remove::<HiddenForPhoto>();
+    }
+}
+
+/// Pans the camera with the pan actions or by dragging with the right mouse button, and zooms
+/// it with the wheel.
+/// Runs on real time since the virtual clock is paused. Once photo mode ends the camera
+/// systems ease it back to following the player at its own zoom.
+fn free_camera_system(
+    time: Res<Time<Real>>,
+    actions: Res<ActionState>,
+    mut motion_events: EventReader<MouseMotion>,
+    mut wheel_events: EventReader<MouseWheel>,
+    mut camera_query: Query<
+        (
+            &Camera,
+            &GlobalTransform,
+            &mut Transform,
+            &mut OrthographicProjection,
+        ),
+        With<CameraZoom>,
+    >,
+    blit_camera_query: Query<&Camera, With<IsDefaultUiCamera>>,
+) {
+    let Ok(blit_camera) = blit_camera_query.get_single() else {
+        return;
+    };
+
+    let mut direction = Vec2::ZERO;
+    for (action, step) in [
+        (Action::PanUp, Vec2::Y),
+        (Action::PanLeft, Vec2::NEG_X),
+        (Action::PanDown, Vec2::NEG_Y),
+        (Action::PanRight, Vec2::X),
+    ] {
+        if actions.pressed(action) {
+            direction += step;
+        }
+    }
+    let dragged: Vec2 = motion_events.read().map(|event| event.delta).sum();
+    let scrolled: f32 = wheel_events
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / 100.0,
+        })
+        .sum();
+
+    for (camera, global_transform, mut transform, mut projection) in &mut camera_query {
+        let mut pan = direction.normalize_or_zero() * PAN_SPEED * projection.scale;
+        pan *= time.delta_seconds();
+
+        if actions.pressed(Action::DragCamera) {
+            // move the world along with the cursor, converting the drag from screen pixels
+            let to_world =
+                |position| window_to_world(position, camera, global_transform, blit_camera);
+            let world_drag = to_world(dragged)
+                .zip(to_world(Vec2::ZERO))
+                .map_or(Vec2::ZERO, |(dragged, origin)| dragged - origin);
+            pan -= world_drag;
+        }
+        transform.translation += pan.extend(0.0);
+
+        projection.scale =
+            (projection.scale * (1.0 - scrolled * ZOOM_STEP)).clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+}
@@ -0,0 +1,104 @@
+//! A bestiary screen that can be accessed from the title screen,
+//! listing every kind of enemy encountered so far.
+
+use bevy::prelude::*;
+
+use super::Screen;
+use crate::{game::bestiary::Bestiary, ui_tools::prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Bestiary), enter_bestiary);
+
+    app.register_type::<BestiaryAction>();
+    app.add_systems(
+        Update,
+        handle_bestiary_action.run_if(in_state(Screen::Bestiary)),
+    );
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum BestiaryAction {
+    Back,
+}
+
+fn enter_bestiary(mut commands: Commands, asset_server: Res<AssetServer>, bestiary: Res<Bestiary>) {
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::Bestiary))
+        .with_children(|children| {
+            if bestiary.entries.is_empty() {
+                children
+                    .label("No enemies encountered yet")
+                    .insert(entry_label_style());
+            }
+
+            for entry in &bestiary.entries {
+                children
+                    .spawn((
+                        Name::new("Bestiary Entry"),
+                        NodeBundle {
+                            style: Style {
+                                align_items: AlignItems::Center,
+                                column_gap: Val::Px(16.0),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                    ))
+                    .with_children(|row| {
+                        // keep the labels lined up when an entry has no art
+                        let mut image = row.spawn((
+                            Name::new("Bestiary Art"),
+                            NodeBundle {
+                                style: Style {
+                                    width: Val::Px(56.0),
+                                    height: Val::Px(56.0),
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                        ));
+                        if let Some(texture) = &entry.texture {
+                            image.insert(UiImage::new(asset_server.load(texture.clone())));
+                        }
+
+                        row.label(format!(
+                            "{}: {} health, {} defeated",
+                            entry.kind.name(),
+                            entry.kind.base_health(),
+                            entry.kills
+                        ))
+                        .insert(entry_label_style());
+                    });
+            }
+
+            children
+                .button("Back")
+                .insert((BestiaryAction::Back, MenuButton { index: 0 }));
+        });
+}
+
+/// Entries need more room than a plain label.
+fn entry_label_style() -> Style {
+    Style {
+        width: Val::Px(640.0),
+        height: Val::Px(65.0),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    }
+}
+
+fn handle_bestiary_action(
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut button_query: InteractionQuery<&BestiaryAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                BestiaryAction::Back => next_screen.set(Screen::Title),
+            }
+        }
+    }
+}
@@ -30,7 +30,9 @@ fn enter_credits(mut commands: Commands) {
             children.label("Alice - Foo");
             children.label("Bob - Bar");
 
-            children.button("Back").insert(CreditsAction::Back);
+            children
+                .button("Back")
+                .insert((CreditsAction::Back, MenuButton { index: 0 }));
         });
 
     commands.trigger(Soundtrack::Credits);
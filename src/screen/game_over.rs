@@ -0,0 +1,126 @@
+//! The results screen shown when the player ship is destroyed.
+
+use bevy::{prelude::*, ui::Val::*};
+
+use super::Screen;
+use crate::{
+    game::stats::{BestRun, RunStats},
+    ui_tools::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::GameOver), enter_game_over);
+
+    app.register_type::<GameOverAction>();
+    app.add_systems(
+        Update,
+        handle_game_over_action.run_if(in_state(Screen::GameOver)),
+    );
+}
+
+/// Text colour for results that beat the previous best.
+const NEW_RECORD_TEXT: Color = Color::srgb(0.4, 1.0, 0.5);
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum GameOverAction {
+    PlayAgain,
+    Title,
+}
+
+fn enter_game_over(mut commands: Commands, stats: Res<RunStats>, mut best: ResMut<BestRun>) {
+    let total_kills = stats.total_kills();
+    let new_survival = stats.survival_secs > best.survival_secs;
+    let new_wave = stats.wave_reached > best.wave_reached;
+    let new_kills = total_kills > best.total_kills;
+
+    best.survival_secs = best.survival_secs.max(stats.survival_secs);
+    best.wave_reached = best.wave_reached.max(stats.wave_reached);
+    best.total_kills = best.total_kills.max(total_kills);
+
+    let mut kills_by_type: Vec<_> = stats.kills.iter().collect();
+    kills_by_type.sort();
+
+    let accuracy = stats.accuracy().map_or("-".to_string(), |accuracy| {
+        format!("{:.0}%", accuracy * 100.0)
+    });
+
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::GameOver))
+        .with_children(|children| {
+            children.label("Game Over");
+
+            result_line(
+                children,
+                format!("Survived {:.1}s", stats.survival_secs),
+                new_survival,
+            );
+            result_line(
+                children,
+                format!("Reached wave {}", stats.wave_reached),
+                new_wave,
+            );
+            result_line(children, format!("{} kills", total_kills), new_kills);
+            for (kind, count) in kills_by_type {
+                result_line(children, format!("{}: {count}", kind.name()), false);
+            }
+            result_line(children, format!("Max combo x{}", stats.max_combo), false);
+            result_line(
+                children,
+                format!("Longest chain reaction x{}", stats.longest_chain),
+                false,
+            );
+            result_line(
+                children,
+                format!("Laser fired for {:.1}s", stats.seconds_fired),
+                false,
+            );
+            result_line(children, format!("Accuracy {accuracy}"), false);
+
+            children
+                .button("Play Again")
+                .insert((GameOverAction::PlayAgain, MenuButton { index: 0 }));
+            children
+                .button("Title")
+                .insert((GameOverAction::Title, MenuButton { index: 1 }));
+        });
+}
+
+fn result_line(children: &mut ChildBuilder, text: String, new_record: bool) {
+    let (text, color) = if new_record {
+        (format!("{text} (new best!)"), NEW_RECORD_TEXT)
+    } else {
+        (text, ui_palette::BUTTON_TEXT)
+    };
+
+    children.spawn((
+        Name::new("Result"),
+        TextBundle::from_section(
+            text,
+            TextStyle {
+                font_size: 28.0,
+                color,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            margin: UiRect::vertical(Px(2.0)),
+            ..default()
+        }),
+    ));
+}
+
+fn handle_game_over_action(
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut button_query: InteractionQuery<&GameOverAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                GameOverAction::PlayAgain => next_screen.set(Screen::ShipSelect),
+                GameOverAction::Title => next_screen.set(Screen::Title),
+            }
+        }
+    }
+}
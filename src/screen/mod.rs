@@ -1,7 +1,11 @@
 //! The game's main screen states and transitions between them.
 
+mod bestiary;
 mod credits;
+mod game_over;
 mod playing;
+mod settings;
+mod ship_select;
 mod splash;
 mod title;
 
@@ -15,7 +19,11 @@ pub(super) fn plugin(app: &mut App) {
         splash::plugin,
         title::plugin,
         credits::plugin,
+        bestiary::plugin,
+        settings::plugin,
+        ship_select::plugin,
         playing::plugin,
+        game_over::plugin,
     ));
 }
 
@@ -26,5 +34,9 @@ pub enum Screen {
     Splash,
     Title,
     Credits,
+    Bestiary,
+    Settings,
+    ShipSelect,
     Playing,
+    GameOver,
 }
@@ -1,9 +1,13 @@
 //! The screen state for the main game loop.
 
-use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use bevy::prelude::*;
 
 use super::Screen;
-use crate::game::{audio::soundtrack::Soundtrack, spawn::level::SpawnLevel};
+use crate::game::{
+    audio::soundtrack::Soundtrack,
+    input::{action_just_pressed, Action},
+    spawn::level::SpawnLevel,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Playing), enter_playing);
@@ -12,7 +16,7 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         Update,
         return_to_title_screen
-            .run_if(in_state(Screen::Playing).and_then(input_just_pressed(KeyCode::Escape))),
+            .run_if(in_state(Screen::Playing).and_then(action_just_pressed(Action::ReturnToTitle))),
     );
 }
 
@@ -0,0 +1,193 @@
+//! A settings screen that can be accessed from the title screen.
+
+use bevy::prelude::*;
+
+use super::Screen;
+use crate::{settings::Settings, ui_tools::prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Settings), enter_settings);
+
+    app.register_type::<SettingsAction>();
+    app.add_systems(
+        Update,
+        (handle_settings_action, update_settings_labels)
+            .chain()
+            .run_if(in_state(Screen::Settings)),
+    );
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum SettingsAction {
+    CycleMsaa,
+    ToggleTutorial,
+    ToggleBackground,
+    CyclePerformance,
+    CycleRenderScale,
+    ToggleCrispUpscaling,
+    ToggleReducedMotion,
+    Back,
+}
+
+fn enter_settings(mut commands: Commands, settings: Res<Settings>) {
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::Settings))
+        .with_children(|children| {
+            children
+                .button(msaa_label(&settings))
+                .insert((SettingsAction::CycleMsaa, MenuButton { index: 0 }))
+                .insert(wide_button_style());
+
+            children
+                .button(tutorial_label(&settings))
+                .insert((SettingsAction::ToggleTutorial, MenuButton { index: 1 }))
+                .insert(wide_button_style());
+
+            children
+                .button(background_label(&settings))
+                .insert((SettingsAction::ToggleBackground, MenuButton { index: 2 }))
+                .insert(wide_button_style());
+
+            children
+                .button(performance_label(&settings))
+                .insert((SettingsAction::CyclePerformance, MenuButton { index: 3 }))
+                .insert(wide_button_style());
+
+            children
+                .button(render_scale_label(&settings))
+                .insert((SettingsAction::CycleRenderScale, MenuButton { index: 4 }))
+                .insert(wide_button_style());
+
+            children
+                .button(upscaling_label(&settings))
+                .insert((
+                    SettingsAction::ToggleCrispUpscaling,
+                    MenuButton { index: 5 },
+                ))
+                .insert(wide_button_style());
+
+            children
+                .button(reduced_motion_label(&settings))
+                .insert((SettingsAction::ToggleReducedMotion, MenuButton { index: 6 }))
+                .insert(wide_button_style());
+
+            children
+                .button("Back")
+                .insert((SettingsAction::Back, MenuButton { index: 7 }));
+        });
+}
+
+/// Settings buttons show their current value, so they need more room than a plain button.
+fn wide_button_style() -> Style {
+    Style {
+        width: Val::Px(400.0),
+        height: Val::Px(65.0),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    }
+}
+
+fn msaa_label(settings: &Settings) -> String {
+    format!("Anti-aliasing: {}", settings.msaa.label())
+}
+
+fn on_off_label(value: bool) -> &'static str {
+    if value {
+        "On"
+    } else {
+        "Off"
+    }
+}
+
+fn tutorial_label(settings: &Settings) -> String {
+    format!("Tutorial: {}", on_off_label(settings.show_tutorial))
+}
+
+fn background_label(settings: &Settings) -> String {
+    format!("Background: {}", on_off_label(settings.show_background))
+}
+
+fn performance_label(settings: &Settings) -> String {
+    format!("Performance mode: {}", settings.performance.label())
+}
+
+fn render_scale_label(settings: &Settings) -> String {
+    format!("Resolution: {}", settings.render_scale.label())
+}
+
+fn upscaling_label(settings: &Settings) -> String {
+    let filter = if settings.crisp_upscaling {
+        "Crisp"
+    } else {
+        "Smooth"
+    };
+    format!("Upscaling: {filter}")
+}
+
+fn reduced_motion_label(settings: &Settings) -> String {
+    format!("Reduced motion: {}", on_off_label(settings.reduced_motion))
+}
+
+fn handle_settings_action(
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut settings: ResMut<Settings>,
+    mut button_query: InteractionQuery<&SettingsAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                SettingsAction::CycleMsaa => settings.msaa = settings.msaa.next(),
+                SettingsAction::ToggleTutorial => {
+                    settings.show_tutorial = !settings.show_tutorial;
+                }
+                SettingsAction::ToggleBackground => {
+                    settings.show_background = !settings.show_background;
+                }
+                SettingsAction::CyclePerformance => {
+                    settings.performance = settings.performance.next();
+                }
+                SettingsAction::CycleRenderScale => {
+                    settings.render_scale = settings.render_scale.next();
+                }
+                SettingsAction::ToggleCrispUpscaling => {
+                    settings.crisp_upscaling = !settings.crisp_upscaling;
+                }
+                SettingsAction::ToggleReducedMotion => {
+                    settings.reduced_motion = !settings.reduced_motion;
+                }
+                SettingsAction::Back => next_screen.set(Screen::Title),
+            }
+        }
+    }
+}
+
+/// Keeps the button labels in sync with the current settings.
+fn update_settings_labels(
+    settings: Res<Settings>,
+    button_query: Query<(&SettingsAction, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for (action, children) in &button_query {
+        let label = match action {
+            SettingsAction::CycleMsaa => msaa_label(&settings),
+            SettingsAction::ToggleTutorial => tutorial_label(&settings),
+            SettingsAction::ToggleBackground => background_label(&settings),
+            SettingsAction::CyclePerformance => performance_label(&settings),
+            SettingsAction::CycleRenderScale => render_scale_label(&settings),
+            SettingsAction::ToggleCrispUpscaling => upscaling_label(&settings),
+            SettingsAction::ToggleReducedMotion => reduced_motion_label(&settings),
+            SettingsAction::Back => continue,
+        };
+        let mut texts = text_query.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            text.sections[0].value.clone_from(&label);
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! The ship selection screen shown before a run starts.
+
+use bevy::{ecs::system::EntityCommands, prelude::*, ui::Val::*};
+
+use super::Screen;
+use crate::{
+    game::ship::{SelectedShip, SHIPS},
+    ui_tools::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::ShipSelect), enter_ship_select);
+
+    app.register_type::<ShipSelectAction>();
+    app.add_systems(
+        Update,
+        handle_ship_select_action.run_if(in_state(Screen::ShipSelect)),
+    );
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum ShipSelectAction {
+    /// Index into [`SHIPS`].
+    Pick(usize),
+    Back,
+}
+
+fn enter_ship_select(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::ShipSelect))
+        .with_children(|children| {
+            children.label("Choose a ship");
+
+            children
+                .spawn((
+                    Name::new("Ship Cards"),
+                    NodeBundle {
+                        style: Style {
+                            column_gap: Px(20.0),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                ))
+                .with_children(|children| {
+                    for (index, ship) in SHIPS.iter().enumerate() {
+                        spawn_ship_card(children, &asset_server, index)
+                            .insert(Name::new(ship.name));
+                    }
+                });
+
+            children
+                .button("Back")
+                .insert((ShipSelectAction::Back, MenuButton { index: SHIPS.len() }));
+        });
+}
+
+/// A button showing a ship's sprite and stats.
+fn spawn_ship_card<'a>(
+    children: &'a mut ChildBuilder,
+    asset_server: &AssetServer,
+    index: usize,
+) -> EntityCommands<'a> {
+    let ship = &SHIPS[index];
+    let stats = format!(
+        "{}\n\nHealth {}\nSpeed {}\nTurn {}\nLaser {} dps\nRange {}",
+        ship.name,
+        ship.max_health,
+        ship.movement_speed,
+        ship.rotation_speed,
+        ship.laser_damage_per_second,
+        ship.laser_max_length,
+    );
+
+    let mut entity = children.spawn((
+        ButtonBundle {
+            style: Style {
+                width: Px(220.0),
+                padding: UiRect::all(Px(16.0)),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Px(12.0),
+                ..default()
+            },
+            background_color: BackgroundColor(ui_palette::NODE_BACKGROUND),
+            ..default()
+        },
+        InteractionPalette {
+            none: ui_palette::NODE_BACKGROUND,
+            hovered: ui_palette::BUTTON_HOVERED_BACKGROUND,
+            pressed: ui_palette::BUTTON_PRESSED_BACKGROUND,
+        },
+        ShipSelectAction::Pick(index),
+        MenuButton { index },
+    ));
+    entity.with_children(|children| {
+        children.spawn((
+            Name::new("Ship Sprite"),
+            ImageBundle {
+                style: Style {
+                    width: Px(96.0),
+                    height: Px(96.0),
+                    ..default()
+                },
+                image: UiImage::new(asset_server.load(ship.texture)).with_color(ship.color),
+                ..default()
+            },
+        ));
+        children.spawn((
+            Name::new("Ship Stats"),
+            TextBundle::from_section(
+                stats,
+                TextStyle {
+                    font_size: 24.0,
+                    color: ui_palette::BUTTON_TEXT,
+                    ..default()
+                },
+            ),
+        ));
+    });
+    entity
+}
+
+fn handle_ship_select_action(
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut selected_ship: ResMut<SelectedShip>,
+    mut button_query: InteractionQuery<&ShipSelectAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                ShipSelectAction::Pick(index) => {
+                    selected_ship.0 = *index;
+                    next_screen.set(Screen::Playing);
+                }
+                ShipSelectAction::Back => next_screen.set(Screen::Title),
+            }
+        }
+    }
+}
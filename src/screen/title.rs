@@ -16,6 +16,8 @@ pub(super) fn plugin(app: &mut App) {
 #[reflect(Component)]
 enum TitleAction {
     Play,
+    Settings,
+    Bestiary,
     Credits,
 }
 
@@ -24,8 +26,18 @@ fn enter_title(mut commands: Commands) {
         .ui_root()
         .insert(StateScoped(Screen::Title))
         .with_children(|children| {
-            children.button("Play").insert(TitleAction::Play);
-            children.button("Credits").insert(TitleAction::Credits);
+            children
+                .button("Play")
+                .insert((TitleAction::Play, MenuButton { index: 0 }));
+            children
+                .button("Settings")
+                .insert((TitleAction::Settings, MenuButton { index: 1 }));
+            children
+                .button("Bestiary")
+                .insert((TitleAction::Bestiary, MenuButton { index: 2 }));
+            children
+                .button("Credits")
+                .insert((TitleAction::Credits, MenuButton { index: 3 }));
         });
 }
 
@@ -36,7 +48,9 @@ fn handle_title_action(
     for (interaction, action) in &mut button_query {
         if matches!(interaction, Interaction::Pressed) {
             match action {
-                TitleAction::Play => next_screen.set(Screen::Playing),
+                TitleAction::Play => next_screen.set(Screen::ShipSelect),
+                TitleAction::Settings => next_screen.set(Screen::Settings),
+                TitleAction::Bestiary => next_screen.set(Screen::Bestiary),
                 TitleAction::Credits => next_screen.set(Screen::Credits),
             }
         }
@@ -0,0 +1,349 @@
+//! Player settings, saved to disk whenever they change.
+
+use std::{fs, io};
+
+use bevy::prelude::*;
+
+use crate::camera::{CrispUpscaling, RenderScale};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(
+        Settings,
+        MsaaSetting,
+        PerformanceSetting,
+        PerformanceMode,
+        RenderScaleSetting,
+    )>();
+    app.insert_resource(Settings::load());
+    app.init_resource::<PerformanceMode>();
+
+    // Apply settings at startup and whenever they change, saving any changes.
+    app.add_systems(
+        Update,
+        (
+            (apply_msaa, apply_render_scale).run_if(resource_changed::<Settings>),
+            save_settings
+                .run_if(resource_changed::<Settings>.and_then(not(resource_added::<Settings>))),
+        ),
+    );
+
+    // Skip cosmetic effects when asked to, or when the frame rate drops.
+    app.add_systems(Update, update_performance_mode);
+}
+
+/// Where settings are saved between sessions.
+const SETTINGS_PATH: &str = "settings.txt";
+
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct Settings {
+    pub msaa: MsaaSetting,
+    /// Show the tutorial at the start of the next run. Turned off once it is finished or skipped.
+    pub show_tutorial: bool,
+    /// Show the scrolling starfield behind the playfield.
+    pub show_background: bool,
+    pub performance: PerformanceSetting,
+    pub render_scale: RenderScaleSetting,
+    /// Upscale a scaled-down render with sharp pixels instead of smoothing them.
+    pub crisp_upscaling: bool,
+    /// Skip camera motion and flashing effects, such as the punch on kills and damage flashes,
+    /// for players sensitive to motion or flashing.
+    pub reduced_motion: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            msaa: default(),
+            show_tutorial: true,
+            show_background: true,
+            performance: default(),
+            render_scale: default(),
+            crisp_upscaling: false,
+            reduced_motion: false,
+        }
+    }
+}
+
+/// Multisample anti-aliasing levels the player can pick from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum MsaaSetting {
+    Off,
+    Sample2,
+    /// Bevy's default.
+    #[default]
+    Sample4,
+}
+
+impl MsaaSetting {
+    /// The next level, wrapping back around to `Off`.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Off => Self::Sample2,
+            Self::Sample2 => Self::Sample4,
+            Self::Sample4 => Self::Off,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Sample2 => "2x",
+            Self::Sample4 => "4x",
+        }
+    }
+
+    fn from_label(label: &str) -> Option<Self> {
+        [Self::Off, Self::Sample2, Self::Sample4]
+            .into_iter()
+            .find(|setting| setting.label() == label)
+    }
+}
+
+impl From<MsaaSetting> for Msaa {
+    fn from(setting: MsaaSetting) -> Self {
+        match setting {
+            MsaaSetting::Off => Msaa::Off,
+            MsaaSetting::Sample2 => Msaa::Sample2,
+            MsaaSetting::Sample4 => Msaa::Sample4,
+        }
+    }
+}
+
+/// Resolutions the playfield can be rendered at, relative to the window's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum RenderScaleSetting {
+    #[default]
+    Full,
+    ThreeQuarters,
+    Half,
+    Quarter,
+}
+
+impl RenderScaleSetting {
+    /// The next resolution down, wrapping back around to `Full`.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Full => Self::ThreeQuarters,
+            Self::ThreeQuarters => Self::Half,
+            Self::Half => Self::Quarter,
+            Self::Quarter => Self::Full,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Full => "100%",
+            Self::ThreeQuarters => "75%",
+            Self::Half => "50%",
+            Self::Quarter => "25%",
+        }
+    }
+
+    fn from_label(label: &str) -> Option<Self> {
+        [Self::Full, Self::ThreeQuarters, Self::Half, Self::Quarter]
+            .into_iter()
+            .find(|setting| setting.label() == label)
+    }
+}
+
+impl From<RenderScaleSetting> for RenderScale {
+    fn from(setting: RenderScaleSetting) -> Self {
+        match setting {
+            RenderScaleSetting::Full => RenderScale(1.0),
+            RenderScaleSetting::ThreeQuarters => RenderScale(0.75),
+            RenderScaleSetting::Half => RenderScale(0.5),
+            RenderScaleSetting::Quarter => RenderScale(0.25),
+        }
+    }
+}
+
+/// When to turn on [`PerformanceMode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum PerformanceSetting {
+    Off,
+    /// Whenever the frame rate stays low.
+    #[default]
+    Auto,
+    On,
+}
+
+impl PerformanceSetting {
+    /// The next option, wrapping back around to `Off`.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Off => Self::Auto,
+            Self::Auto => Self::On,
+            Self::On => Self::Off,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Auto => "Auto",
+            Self::On => "On",
+        }
+    }
+
+    fn from_label(label: &str) -> Option<Self> {
+        [Self::Off, Self::Auto, Self::On]
+            .into_iter()
+            .find(|setting| setting.label() == label)
+    }
+}
+
+/// Whether purely cosmetic effects, such as trails, flashes and the starfield, are skipped to
+/// save frame time. Gameplay and the effects that telegraph attacks are never affected.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct PerformanceMode(pub bool);
+
+/// Run condition for cosmetic effects, which are skipped in [`PerformanceMode`].
+pub fn effects_enabled(mode: Res<PerformanceMode>) -> bool {
+    !mode.0
+}
+
+/// `Auto` performance mode turns on once the frame rate has stayed below this for
+/// [`LOW_FRAME_RATE_SECS`], and back off once it has stayed above [`RECOVERED_FRAME_RATE`]
+/// for [`RECOVERED_FRAME_RATE_SECS`].
+const LOW_FRAME_RATE: f32 = 45.0;
+const LOW_FRAME_RATE_SECS: f32 = 2.0;
+const RECOVERED_FRAME_RATE: f32 = 58.0;
+const RECOVERED_FRAME_RATE_SECS: f32 = 5.0;
+
+/// How long the frame rate has been on the other side of the threshold for the current mode.
+#[derive(Default)]
+struct FrameRateWatch {
+    /// Frame rate, smoothed over a few frames so single hitches don't count.
+    smoothed: Option<f32>,
+    secs_past_threshold: f32,
+}
+
+fn update_performance_mode(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut mode: ResMut<PerformanceMode>,
+    mut watch: Local<FrameRateWatch>,
+) {
+    let enabled = match settings.performance {
+        PerformanceSetting::Off => false,
+        PerformanceSetting::On => true,
+        PerformanceSetting::Auto => {
+            let delta = time.delta_seconds();
+            if delta <= 0.0 {
+                return;
+            }
+            let frame_rate = 1.0 / delta;
+            let smoothed = watch
+                .smoothed
+                .map_or(frame_rate, |smoothed| smoothed.lerp(frame_rate, 0.1));
+            watch.smoothed = Some(smoothed);
+
+            let (past_threshold, secs_needed) = if mode.0 {
+                (smoothed > RECOVERED_FRAME_RATE, RECOVERED_FRAME_RATE_SECS)
+            } else {
+                (smoothed < LOW_FRAME_RATE, LOW_FRAME_RATE_SECS)
+            };
+            watch.secs_past_threshold = if past_threshold {
+                watch.secs_past_threshold + delta
+            } else {
+                0.0
+            };
+
+            if watch.secs_past_threshold < secs_needed {
+                return;
+            }
+            watch.secs_past_threshold = 0.0;
+            !mode.0
+        }
+    };
+
+    if mode.set_if_neq(PerformanceMode(enabled)) {
+        info!("Performance mode {}", if enabled { "on" } else { "off" });
+    }
+}
+
+impl Settings {
+    /// Loads the saved settings, falling back to the defaults for anything missing or invalid.
+    fn load() -> Self {
+        let mut settings = Self::default();
+        let contents = match fs::read_to_string(SETTINGS_PATH) {
+            Ok(contents) => contents,
+            Err(error) => {
+                if error.kind() != io::ErrorKind::NotFound {
+                    warn!("Failed to load settings: {error}");
+                }
+                return settings;
+            }
+        };
+
+        // one `key = value` pair per line
+        for line in contents.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                "msaa" => {
+                    if let Some(msaa) = MsaaSetting::from_label(value) {
+                        settings.msaa = msaa;
+                    }
+                }
+                "tutorial" => settings.show_tutorial = value == "on",
+                "background" => settings.show_background = value == "on",
+                "performance" => {
+                    if let Some(performance) = PerformanceSetting::from_label(value) {
+                        settings.performance = performance;
+                    }
+                }
+                "render_scale" => {
+                    if let Some(render_scale) = RenderScaleSetting::from_label(value) {
+                        settings.render_scale = render_scale;
+                    }
+                }
+                "crisp_upscaling" => settings.crisp_upscaling = value == "on",
+                "reduced_motion" => settings.reduced_motion = value == "on",
+                key => warn!("Unknown setting `{key}`"),
+            }
+        }
+        settings
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let on_off = |value| if value { "on" } else { "off" };
+        fs::write(
+            SETTINGS_PATH,
+            format!(
+                "msaa = {}\ntutorial = {}\nbackground = {}\nperformance = {}\n\
+                 render_scale = {}\ncrisp_upscaling = {}\nreduced_motion = {}\n",
+                self.msaa.label(),
+                on_off(self.show_tutorial),
+                on_off(self.show_background),
+                self.performance.label(),
+                self.render_scale.label(),
+                on_off(self.crisp_upscaling),
+                on_off(self.reduced_motion),
+            ),
+        )
+    }
+}
+
+fn apply_msaa(settings: Res<Settings>, mut msaa: ResMut<Msaa>) {
+    msaa.set_if_neq(settings.msaa.into());
+}
+
+fn apply_render_scale(
+    settings: Res<Settings>,
+    mut render_scale: ResMut<RenderScale>,
+    mut crisp: ResMut<CrispUpscaling>,
+) {
+    render_scale.set_if_neq(settings.render_scale.into());
+    crisp.set_if_neq(CrispUpscaling(settings.crisp_upscaling));
+}
+
+fn save_settings(settings: Res<Settings>) {
+    if let Err(error) = settings.save() {
+        warn!("Failed to save settings: {error}");
+    }
+}
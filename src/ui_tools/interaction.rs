@@ -17,7 +17,7 @@ pub struct InteractionPalette {
     pub pressed: Color,
 }
 
-fn apply_interaction_palette(
+pub(super) fn apply_interaction_palette(
     mut palette_query: InteractionQuery<(&InteractionPalette, &mut BackgroundColor)>,
 ) {
     for (interaction, (palette, mut background)) in &mut palette_query {
@@ -4,12 +4,14 @@
 #![allow(dead_code, unused_imports)]
 
 pub mod interaction;
+pub mod navigation;
 pub mod palette;
 mod widgets;
 
 pub mod prelude {
     pub use super::{
         interaction::{InteractionPalette, InteractionQuery},
+        navigation::MenuButton,
         palette as ui_palette,
         widgets::{Containers as _, Widgets as _},
     };
@@ -18,5 +20,5 @@ pub mod prelude {
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(interaction::plugin);
+    app.add_plugins((interaction::plugin, navigation::plugin));
 }
@@ -0,0 +1,156 @@
+//! Keyboard and gamepad navigation between menu buttons.
+
+use bevy::{prelude::*, ui::UiSystem};
+
+use super::interaction::{apply_interaction_palette, InteractionPalette};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(MenuFocus, MenuButton)>();
+    app.init_resource::<MenuFocus>();
+    app.add_systems(
+        PreUpdate,
+        (reset_menu_focus, move_menu_focus, activate_focused_button)
+            .chain()
+            .after(UiSystem::Focus),
+    );
+    app.add_systems(
+        Update,
+        highlight_focused_button.after(apply_interaction_palette),
+    );
+}
+
+/// The [`MenuButton::index`] of the button that keyboard and gamepad input act on.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct MenuFocus {
+    pub index: usize,
+}
+
+/// A button that can be focused with the keyboard or a gamepad.
+/// Buttons are visited in order of `index`, which should be unique on screen.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct MenuButton {
+    pub index: usize,
+}
+
+/// How far the left stick has to be pushed to move the focus.
+const STICK_THRESHOLD: f32 = 0.5;
+
+/// Focuses the first button whenever a new menu appears.
+fn reset_menu_focus(mut focus: ResMut<MenuFocus>, added_query: Query<(), Added<MenuButton>>) {
+    if !added_query.is_empty() {
+        focus.index = 0;
+    }
+}
+
+/// Moves the focus with the arrow keys, the d-pad or the left stick,
+/// and follows the mouse when it hovers a button.
+fn move_menu_focus(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepads: Res<Gamepads>,
+    mut stick_held: Local<bool>,
+    mut focus: ResMut<MenuFocus>,
+    button_query: Query<(&MenuButton, &Interaction)>,
+) {
+    let count = button_query.iter().count();
+    if count == 0 {
+        return;
+    }
+
+    for (button, interaction) in &button_query {
+        if *interaction == Interaction::Hovered {
+            focus.index = button.index;
+        }
+    }
+
+    let mut step = 0;
+    if keyboard.any_just_pressed([KeyCode::ArrowDown, KeyCode::ArrowRight, KeyCode::Tab]) {
+        step += 1;
+    }
+    if keyboard.any_just_pressed([KeyCode::ArrowUp, KeyCode::ArrowLeft]) {
+        step -= 1;
+    }
+
+    let mut stick = 0.0;
+    for gamepad in gamepads.iter() {
+        let pressed =
+            |button_type| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button_type));
+        if pressed(GamepadButtonType::DPadDown) || pressed(GamepadButtonType::DPadRight) {
+            step += 1;
+        }
+        if pressed(GamepadButtonType::DPadUp) || pressed(GamepadButtonType::DPadLeft) {
+            step -= 1;
+        }
+
+        let axis = GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY);
+        stick += gamepad_axes.get(axis).unwrap_or_default();
+    }
+
+    // only step once per push of the stick
+    if stick.abs() < STICK_THRESHOLD {
+        *stick_held = false;
+    } else if !*stick_held {
+        *stick_held = true;
+        step -= stick.signum() as i32;
+    }
+
+    focus.index = (focus.index as i32 + step).rem_euclid(count as i32) as usize;
+}
+
+/// Presses the focused button on Enter or the gamepad's south button.
+/// Screens react to it exactly as if it had been clicked.
+fn activate_focused_button(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    focus: Res<MenuFocus>,
+    mut pressed_button: Local<Option<Entity>>,
+    mut button_query: Query<(Entity, &MenuButton, &mut Interaction)>,
+) {
+    // release the button pressed last frame, since the mouse won't
+    if let Some((_, _, mut interaction)) = pressed_button
+        .take()
+        .and_then(|entity| button_query.get_mut(entity).ok())
+    {
+        interaction.set_if_neq(Interaction::None);
+    }
+
+    let activate = keyboard.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter])
+        || gamepad_buttons
+            .get_just_pressed()
+            .any(|button| button.button_type == GamepadButtonType::South);
+    if !activate {
+        return;
+    }
+
+    for (entity, button, mut interaction) in &mut button_query {
+        if button.index == focus.index {
+            *interaction = Interaction::Pressed;
+            *pressed_button = Some(entity);
+        }
+    }
+}
+
+/// Shows the focused button as hovered when the mouse isn't interacting with it.
+fn highlight_focused_button(
+    focus: Res<MenuFocus>,
+    mut button_query: Query<(
+        &MenuButton,
+        &Interaction,
+        &InteractionPalette,
+        &mut BackgroundColor,
+    )>,
+) {
+    for (button, interaction, palette, mut background) in &mut button_query {
+        if *interaction == Interaction::None {
+            let color = if button.index == focus.index {
+                palette.hovered
+            } else {
+                palette.none
+            };
+            background.set_if_neq(BackgroundColor(color));
+        }
+    }
+}